@@ -0,0 +1,64 @@
+/// Watching a run for goals as it's played
+pub mod tracker;
+/// Saved unlocks and listing them
+pub mod unlocks;
+
+pub use tracker::Tracker;
+pub use unlocks::Unlocks;
+
+use crate::render::sprites::ObstacleType;
+
+/// What it takes to unlock an achievement
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Goal {
+    /// Duck under an obstacle of this type without hitting it
+    DuckUnder(ObstacleType),
+    /// Clear this many obstacles in a row without a hit
+    Streak(u32),
+    /// Reach this score in one run without hitting anything
+    CleanScore(u32),
+    /// Play during this many compactions, counting the current one
+    Compactions(u32),
+}
+
+/// One entry in the catalog
+#[derive(Debug, PartialEq)]
+pub struct Achievement {
+    /// Key in the unlocks file; never change it once shipped
+    pub id: &'static str,
+    /// Title shown in the toast and the list
+    pub name: &'static str,
+    /// What it takes, in a few words
+    pub description: &'static str,
+    /// When it unlocks
+    pub goal: Goal,
+}
+
+/// Every achievement, in the order `clawd-runner achievements` lists them
+/// Adding one is a matter of adding a row here.
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "duck_flying",
+        name: "Limbo",
+        description: "Duck under a flying obstacle",
+        goal: Goal::DuckUnder(ObstacleType::Flying),
+    },
+    Achievement {
+        id: "streak_10",
+        name: "Ten in a row",
+        description: "Clear 10 obstacles in a row without a hit",
+        goal: Goal::Streak(10),
+    },
+    Achievement {
+        id: "clean_1000",
+        name: "Untouchable",
+        description: "Reach 1000 points without a hit",
+        goal: Goal::CleanScore(1000),
+    },
+    Achievement {
+        id: "compactions_50",
+        name: "Regular",
+        description: "Play during 50 compactions",
+        goal: Goal::Compactions(50),
+    },
+];
@@ -0,0 +1,141 @@
+use super::{ACHIEVEMENTS, Achievement, Goal, Unlocks};
+use crate::game::{GameEvent, GameState};
+
+/// Watches a run's events and reports achievements as they unlock
+#[derive(Debug)]
+pub struct Tracker {
+    locked: Vec<&'static Achievement>,
+    compactions: u32,
+    streak: u32, // Obstacles cleared since the last hit
+}
+
+impl Tracker {
+    /// Track everything not unlocked yet; `compactions` counts the ones
+    /// played through so far, including the current run's
+    pub fn new(unlocks: &Unlocks, compactions: u32) -> Self {
+        Self {
+            locked: ACHIEVEMENTS
+                .iter()
+                .filter(|a| !unlocks.is_unlocked(a.id))
+                .collect(),
+            compactions,
+            streak: 0,
+        }
+    }
+
+    /// Check the game after a tick; returns what just unlocked
+    /// Nothing counts while the autopilot has the controls.
+    pub fn observe(&mut self, game: &GameState) -> Vec<&'static Achievement> {
+        if game.demo {
+            self.streak = 0;
+            return Vec::new();
+        }
+
+        let mut ducked_under = Vec::new();
+        for event in &game.events {
+            match *event {
+                GameEvent::Cleared {
+                    obstacle_type,
+                    ducked,
+                } => {
+                    self.streak += 1;
+                    if ducked {
+                        ducked_under.push(obstacle_type);
+                    }
+                }
+                GameEvent::Collision(_) => self.streak = 0,
+            }
+        }
+
+        let done = |a: &&Achievement| match a.goal {
+            Goal::DuckUnder(obstacle_type) => ducked_under.contains(&obstacle_type),
+            Goal::Streak(n) => self.streak >= n,
+            Goal::CleanScore(n) => game.collisions == 0 && game.player_score() >= n,
+            Goal::Compactions(n) => self.compactions >= n,
+        };
+        let unlocked: Vec<_> = self.locked.iter().copied().filter(done).collect();
+        self.locked.retain(|a| !unlocked.contains(a));
+        unlocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::Obstacle;
+    use crate::render::sprites::ObstacleType;
+
+    fn ids(unlocked: &[&Achievement]) -> Vec<&'static str> {
+        unlocked.iter().map(|a| a.id).collect()
+    }
+
+    /// Tick until every obstacle on screen has gone past the player
+    fn run_past(game: &mut GameState, tracker: &mut Tracker) -> Vec<&'static str> {
+        let mut unlocked = Vec::new();
+        while game.obstacles.iter().any(|o| !o.passed) {
+            game.tick();
+            unlocked.extend(ids(&tracker.observe(game)));
+        }
+        unlocked
+    }
+
+    #[test]
+    fn ducking_under_flying_unlocks_once() {
+        let mut tracker = Tracker::new(&Unlocks::default(), 0);
+        let mut game = GameState::new();
+        game.toggle_duck();
+        game.obstacles
+            .push(Obstacle::new(30.0, ObstacleType::Flying));
+        assert_eq!(run_past(&mut game, &mut tracker), ["duck_flying"]);
+
+        game.obstacles
+            .push(Obstacle::new(30.0, ObstacleType::Flying));
+        assert!(run_past(&mut game, &mut tracker).is_empty());
+    }
+
+    #[test]
+    fn a_hit_resets_the_streak() {
+        let mut tracker = Tracker::new(&Unlocks::default(), 0);
+        let mut game = GameState::new();
+        let cleared = GameEvent::Cleared {
+            obstacle_type: ObstacleType::Small,
+            ducked: false,
+        };
+
+        game.events = vec![cleared; 9];
+        assert!(tracker.observe(&game).is_empty());
+        game.events = vec![GameEvent::Collision(ObstacleType::Tall), cleared];
+        assert!(tracker.observe(&game).is_empty());
+        game.events = vec![cleared; 9];
+        assert_eq!(ids(&tracker.observe(&game)), ["streak_10"]);
+    }
+
+    #[test]
+    fn clean_score_needs_no_hits() {
+        let mut tracker = Tracker::new(&Unlocks::default(), 0);
+        let mut game = GameState::new();
+        game.score = 1000;
+        game.collisions = 1;
+        assert!(tracker.observe(&game).is_empty());
+        game.collisions = 0;
+        assert_eq!(ids(&tracker.observe(&game)), ["clean_1000"]);
+    }
+
+    #[test]
+    fn autopilot_earns_nothing() {
+        let mut tracker = Tracker::new(&Unlocks::default(), 50);
+        let mut game = GameState::new();
+        game.demo = true;
+        assert!(tracker.observe(&game).is_empty());
+        game.demo = false;
+        assert_eq!(ids(&tracker.observe(&game)), ["compactions_50"]);
+    }
+
+    #[test]
+    fn saved_unlocks_stay_quiet() {
+        let mut unlocks = Unlocks::default();
+        unlocks.add(&["compactions_50"], std::time::SystemTime::now());
+        let mut tracker = Tracker::new(&unlocks, 80);
+        assert!(tracker.observe(&GameState::new()).is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::ACHIEVEMENTS;
+use crate::storage::{self, Versioned, read_versioned, update_versioned};
+
+/// Achievements unlocked so far, stored under the data dir
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Unlocks {
+    version: u32,
+    /// Achievement id to when it unlocked, in Unix seconds
+    unlocked: BTreeMap<String, u64>,
+}
+
+impl Default for Unlocks {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            unlocked: BTreeMap::new(),
+        }
+    }
+}
+
+impl Unlocks {
+    /// `achievements.json` in the data dir
+    pub fn path() -> io::Result<PathBuf> {
+        Ok(storage::data_dir()?.join("achievements.json"))
+    }
+
+    /// Load saved unlocks; a missing or unreadable file starts empty
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| Self::read(&path))
+            .unwrap_or_default()
+    }
+
+    /// Read an unlocks file, treating a missing one as empty and refusing
+    /// one we can't parse
+    pub fn read(path: &Path) -> io::Result<Self> {
+        read_versioned(path)
+    }
+
+    /// True once the achievement with this id has unlocked
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains_key(id)
+    }
+
+    /// When `id` unlocked, in Unix seconds
+    pub fn unlocked_at(&self, id: &str) -> Option<u64> {
+        self.unlocked.get(id).copied()
+    }
+
+    /// Mark `ids` unlocked at `at`, keeping the earlier time for any
+    /// already unlocked
+    pub fn add(&mut self, ids: &[&str], at: SystemTime) {
+        let secs = at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        for id in ids {
+            self.unlocked.entry(id.to_string()).or_insert(secs);
+        }
+    }
+
+    /// Add unlocks to the file at `path`, under a lock like `ScoreBook::record`
+    pub fn record(path: &Path, ids: &[&str], at: SystemTime) -> io::Result<()> {
+        update_versioned(path, |unlocks: &mut Self| unlocks.add(ids, at))
+    }
+}
+
+impl Versioned for Unlocks {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+/// The catalog with what's unlocked, for `clawd-runner achievements`
+impl fmt::Display for Unlocks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let done = ACHIEVEMENTS
+            .iter()
+            .filter(|a| self.is_unlocked(a.id))
+            .count();
+        writeln!(f, "{done}/{} unlocked", ACHIEVEMENTS.len())?;
+        for achievement in ACHIEVEMENTS {
+            let (mark, date) = match self.unlocked_at(achievement.id) {
+                Some(secs) => ("✓", format_date(secs)),
+                None => ("·", String::new()),
+            };
+            writeln!(
+                f,
+                "{mark} {:<14} {date:<10} {}",
+                achievement.name, achievement.description
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Unix seconds as a UTC "YYYY-MM-DD"
+fn format_date(secs: u64) -> String {
+    // Civil-from-days over 400-year eras, which start on March 1st
+    let days = secs / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_are_utc_days() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_700_000_000), "2023-11-14");
+        assert_eq!(format_date(1_709_251_199), "2024-02-29");
+    }
+}
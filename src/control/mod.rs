@@ -0,0 +1,4 @@
+/// The per-session Unix socket and its messages
+pub mod socket;
+
+pub use socket::{ControlMessage, ControlSocket, SocketGuard, join, signal};
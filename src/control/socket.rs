@@ -0,0 +1,217 @@
+use clap::ValueEnum;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use crate::hook::HookInput;
+use crate::watcher::WatchEvent;
+use crate::watcher::sessions::session_label;
+
+/// Prefix of the request a second hook sends to join a running game
+const JOIN: &str = "join ";
+
+/// One-line messages a later hook can send to a running game
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ControlMessage {
+    /// Compaction finished, wrap up the game
+    Done,
+}
+
+impl fmt::Display for ControlMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControlMessage::Done => write!(f, "done"),
+        }
+    }
+}
+
+impl FromStr for ControlMessage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "done" => Ok(ControlMessage::Done),
+            other => Err(format!("unknown control message {other:?}")),
+        }
+    }
+}
+
+/// Per-session Unix socket the game listens on
+#[derive(Debug)]
+pub struct ControlSocket {
+    session_id: String,
+    path: PathBuf,
+    listener: UnixListener,
+}
+
+impl ControlSocket {
+    /// Bind the socket for a session
+    /// A leftover socket nobody is listening on is replaced; a live one
+    /// means another runner already owns this session
+    pub fn bind(session_id: &str) -> io::Result<Self> {
+        let path = socket_path(session_id)?;
+        if path.exists() {
+            if UnixStream::connect(&path).is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!("a runner is already listening on {}", path.display()),
+                ));
+            }
+            fs::remove_file(&path)?;
+        }
+        let listener = UnixListener::bind(&path)?;
+        Ok(Self {
+            session_id: session_id.to_string(),
+            path,
+            listener,
+        })
+    }
+
+    /// Accept connections on a background thread, forwarding `done` as a
+    /// completion event for this session and `join` as a new session to watch.
+    /// The returned guard removes the socket file when dropped.
+    pub fn listen(self, tx: Sender<WatchEvent>) -> SocketGuard {
+        let guard = SocketGuard {
+            path: self.path.clone(),
+        };
+        thread::spawn(move || {
+            for stream in self.listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let mut line = String::new();
+                if BufReader::new(&stream).read_line(&mut line).is_err() {
+                    continue;
+                }
+                let event = match line.strip_prefix(JOIN) {
+                    Some(payload) => join_event(payload),
+                    None => line.parse::<ControlMessage>().map(|ControlMessage::Done| {
+                        WatchEvent::CompactionComplete(self.session_id.clone())
+                    }),
+                };
+                let reply = match event {
+                    Ok(event) => {
+                        if tx.send(event).is_err() {
+                            return;
+                        }
+                        "ok".to_string()
+                    }
+                    Err(err) => err,
+                };
+                let _ = writeln!(&stream, "{reply}");
+            }
+        });
+        guard
+    }
+}
+
+/// Removes the socket file when the game exits
+#[derive(Debug)]
+pub struct SocketGuard {
+    path: PathBuf,
+}
+
+impl Drop for SocketGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Turn a forwarded hook payload into a `SessionJoined` event
+fn join_event(payload: &str) -> Result<WatchEvent, String> {
+    let input: HookInput = serde_json::from_str(payload).map_err(|err| err.to_string())?;
+    let session_id = input.session_id.clone().ok_or("join without session_id")?;
+    let transcript = input.transcript();
+    let label = match transcript {
+        Some(ref path) => session_label(input.cwd.as_deref(), path),
+        None => session_id.clone(),
+    };
+    Ok(WatchEvent::SessionJoined {
+        session_id,
+        label,
+        transcript,
+    })
+}
+
+/// Send a message to the game running for a session
+/// Returns false if no game is listening, which is the normal case when
+/// the player already quit or the hook fired without a game
+pub fn signal(session_id: &str, message: ControlMessage) -> io::Result<bool> {
+    send(&socket_path(session_id)?, &message.to_string())
+}
+
+/// Hand a hook payload to whichever game is already running
+/// Returns false if there isn't one, so the caller should launch its own
+pub fn join(payload: &str) -> io::Result<bool> {
+    let dir = socket_dir()?;
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "sock")
+            && send(&path, &format!("{JOIN}{payload}")).unwrap_or(false)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Write one request line and wait for the reply
+fn send(path: &Path, request: &str) -> io::Result<bool> {
+    let mut stream = match UnixStream::connect(path) {
+        Ok(stream) => stream,
+        Err(err)
+            if matches!(
+                err.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused
+            ) =>
+        {
+            return Ok(false);
+        }
+        Err(err) => return Err(err),
+    };
+    writeln!(stream, "{request}")?;
+
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply)?;
+    match reply.trim() {
+        "ok" => Ok(true),
+        other => Err(io::Error::other(other.to_string())),
+    }
+}
+
+/// `$XDG_RUNTIME_DIR/clawd-runner`, or a per-user directory under the
+/// temp dir when there's no runtime dir
+fn socket_dir() -> io::Result<PathBuf> {
+    let dir = match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(runtime) => PathBuf::from(runtime).join("clawd-runner"),
+        None => {
+            let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+            std::env::temp_dir().join(format!("clawd-runner-{user}"))
+        }
+    };
+    fs::create_dir_all(&dir)?;
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+    Ok(dir)
+}
+
+/// `<socket dir>/<session>.sock`
+fn socket_path(session_id: &str) -> io::Result<PathBuf> {
+    let dir = socket_dir()?;
+
+    // Session ids are UUIDs, but they end up in a path so keep them tame
+    let name: String = session_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Ok(dir.join(format!("{name}.sock")))
+}
@@ -1,5 +1,12 @@
+/// The run's data: player, obstacles, score and phase
 pub mod state;
+/// Movement, collisions and scoring for one tick
 pub mod physics;
+/// When and what obstacles spawn
 pub mod spawner;
+/// The seeded random number generator
+pub mod rng;
+/// Turning elapsed time into fixed ticks
+pub mod timestep;
 
-pub use state::GameState;
+pub use state::{EndReason, GameEvent, GameState};
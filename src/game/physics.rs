@@ -1,4 +1,4 @@
-use super::state::{GameState, PlayerState};
+use super::state::{GameEvent, GamePhase, GameState, PlayerState};
 
 /// Gravity constant (pulls velocity down each tick)
 const GRAVITY: f32 = 0.6;
@@ -9,20 +9,41 @@ const JUMP_VELOCITY: f32 = 5.0;
 /// Maximum speed multiplier
 const MAX_SPEED: f32 = 2.5;
 
-/// Speed increase per frame
+/// Speed increase per tick
 const SPEED_INCREMENT: f32 = 0.0005;
 
 /// Base obstacle scroll speed
 const BASE_SCROLL_SPEED: f32 = 3.0;
 
+/// Ticks per second of game time; every constant above is per tick
+pub const TICKS_PER_SECOND: u64 = 30;
+
 impl GameState {
-    /// Called each frame to update physics
+    /// Advance the game by one tick
     pub fn tick(&mut self) {
+        self.events.clear();
+
+        // Count down the end banner; the world stays frozen unless the
+        // player chose to keep playing
+        if let GamePhase::Ended { ref mut frames_left, keep_playing, .. } = self.phase {
+            *frames_left = frames_left.saturating_sub(1);
+            if !keep_playing {
+                return;
+            }
+        }
+
         self.frame_count += 1;
+        let score_before = self.score;
+
+        // Remember where things were so rendering can blend between ticks
+        self.player.prev_y = self.player.y;
+        for obstacle in &mut self.obstacles {
+            obstacle.prev_x = obstacle.x;
+        }
 
         // Update speed (gradually increases, capped)
         if self.speed < MAX_SPEED {
-            self.speed += SPEED_INCREMENT;
+            self.speed = (self.speed + SPEED_INCREMENT).min(MAX_SPEED);
         }
 
         // Update scroll offset for ground animation
@@ -34,12 +55,18 @@ impl GameState {
         // Move obstacles
         self.update_obstacles();
 
-        // Check collisions
+        // Check collisions, then score whatever got past without one
         self.check_collisions();
+        self.check_cleared();
 
         // Update score (+1 per frame survived)
         self.score += 1;
 
+        // Points earned on autopilot don't belong to the player
+        if self.demo {
+            self.demo_score += self.score - score_before;
+        }
+
         // Check for milestones (100, 500, 1000, 2000, 3000, ...)
         self.check_milestone();
 
@@ -57,6 +84,11 @@ impl GameState {
         if self.score_pop > 0 {
             self.score_pop -= 1;
         }
+
+        // Decrease toast
+        if self.toast_flash > 0 {
+            self.toast_flash -= 1;
+        }
     }
 
     fn check_milestone(&mut self) {
@@ -96,27 +128,36 @@ impl GameState {
     }
 
     fn update_obstacles(&mut self) {
-        let scroll_speed = BASE_SCROLL_SPEED * self.speed;
+        let scroll_speed = self.scroll_speed();
 
         // Move obstacles left
         for obstacle in &mut self.obstacles {
             obstacle.x -= scroll_speed;
+        }
+    }
 
+    fn check_cleared(&mut self) {
+        let player_hitbox_left = self.player.x + 1.0;
+        for obstacle in &mut self.obstacles {
             // Check if player cleared this obstacle (bonus when collision no longer possible)
             // Only award bonus if player didn't collide with it
             let obs_hitbox_right = obstacle.x + 1.0 + obstacle.obstacle_type.hitbox_width() as f32;
-            let player_hitbox_left = self.player.x + 1.0;
             if !obstacle.passed && obs_hitbox_right < player_hitbox_left {
                 obstacle.passed = true;
                 if !obstacle.collided {
                     self.score += 10; // Bonus for clearing obstacle
                     self.score_pop = 8; // Flash score for 8 frames
+                    self.obstacles_cleared += 1;
+                    self.events.push(GameEvent::Cleared {
+                        obstacle_type: obstacle.obstacle_type,
+                        ducked: obstacle.ducked,
+                    });
                 }
             }
         }
 
-        // Remove obstacles that are off-screen
-        self.obstacles.retain(|o| o.x > -20.0);
+        // Remove obstacles once they're fully off the left edge
+        self.obstacles.retain(|o| o.x + o.obstacle_type.width() as f32 > 0.0);
     }
 
     fn check_collisions(&mut self) {
@@ -126,8 +167,10 @@ impl GameState {
         let player_top = player_bottom + self.player.hitbox_height();
 
         for obstacle in &mut self.obstacles {
-            let obs_left = obstacle.x + 1.0; // Slightly inset hitbox
-            let obs_right = obs_left + obstacle.obstacle_type.hitbox_width() as f32;
+            // Slightly inset hitbox, stretched over everywhere it moved this
+            // tick: at top speed it moves further than the overlap is wide
+            let obs_left = obstacle.x + 1.0;
+            let obs_right = obstacle.prev_x + 1.0 + obstacle.obstacle_type.hitbox_width() as f32;
 
             // Flying obstacles are positioned above ground
             let fly_height = obstacle.obstacle_type.fly_height() as f32;
@@ -138,14 +181,33 @@ impl GameState {
             let x_overlap = player_left < obs_right && player_right > obs_left;
             let y_overlap = player_bottom < obs_top && player_top > obs_bottom;
 
+            if x_overlap && self.player.state == PlayerState::Ducking {
+                obstacle.ducked = true;
+            }
+
             if x_overlap && y_overlap {
                 // Collision! Flash but don't die (spec says no death state)
                 self.collision_flash = 12; // ~400ms flash at 30fps
+                if !obstacle.collided {
+                    self.collisions += 1;
+                    self.collisions_by_type.add(obstacle.obstacle_type);
+                    self.events.push(GameEvent::Collision(obstacle.obstacle_type));
+                }
                 obstacle.collided = true; // Prevents bonus for this obstacle
             }
         }
     }
 
+    /// How far obstacles move left per tick
+    pub fn scroll_speed(&self) -> f32 {
+        BASE_SCROLL_SPEED * self.speed
+    }
+
+    /// Seconds of game time elapsed
+    pub fn elapsed_secs(&self) -> u64 {
+        self.frame_count / TICKS_PER_SECOND
+    }
+
     /// Initiate a jump if on the ground
     pub fn jump(&mut self) {
         let can_jump = matches!(
@@ -173,3 +235,226 @@ impl GameState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::Obstacle;
+    use crate::render::sprites::ObstacleType;
+    use proptest::prelude::*;
+
+    /// Upper bound on ticks from takeoff to touchdown, with a little slack
+    /// for the discrete steps
+    fn max_airtime() -> u32 {
+        (2.0 * JUMP_VELOCITY / GRAVITY).ceil() as u32 + 2
+    }
+
+    fn apply(game: &mut GameState, action: u8) {
+        match action {
+            1 => game.jump(),
+            2 => game.toggle_duck(),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn jump_arc_rises_then_lands_with_squash() {
+        let mut game = GameState::new();
+        game.jump();
+        assert_eq!(game.player.state, PlayerState::Jumping);
+
+        game.tick();
+        assert!(game.player.y > 0.0);
+
+        let mut ticks = 1;
+        while game.player.state == PlayerState::Jumping {
+            game.tick();
+            ticks += 1;
+        }
+        assert!(ticks <= max_airtime());
+        assert_eq!(game.player.y, 0.0);
+        assert_eq!(game.player.state, PlayerState::Landing(3));
+
+        // Squash counts down and hands back to running
+        game.tick();
+        assert_eq!(game.player.state, PlayerState::Landing(2));
+        game.tick();
+        assert_eq!(game.player.state, PlayerState::Landing(1));
+        game.tick();
+        assert_eq!(game.player.state, PlayerState::Running);
+    }
+
+    #[test]
+    fn cannot_jump_in_the_air() {
+        let mut game = GameState::new();
+        game.jump();
+        game.tick();
+        let velocity = game.player.velocity_y;
+        game.jump();
+        assert_eq!(game.player.velocity_y, velocity);
+    }
+
+    #[test]
+    fn overlapping_obstacle_collides_once() {
+        let mut game = GameState::new();
+        let x = game.player.x + 2.0 + BASE_SCROLL_SPEED * game.speed;
+        game.obstacles.push(Obstacle::new(x, ObstacleType::Small));
+        game.tick();
+        assert_eq!(game.collisions, 1);
+        assert!(game.obstacles[0].collided);
+        assert!(game.collision_flash > 0);
+
+        // Still overlapping next tick, still one collision
+        game.tick();
+        assert_eq!(game.collisions, 1);
+    }
+
+    #[test]
+    fn cleared_obstacle_awards_bonus() {
+        let mut game = GameState::new();
+        // Just right of the player's hitbox: one tick carries it past
+        game.obstacles.push(Obstacle::new(game.player.x - 1.5, ObstacleType::Small));
+        game.player.y = 10.0;
+        game.player.state = PlayerState::Jumping;
+        game.tick();
+        assert!(game.obstacles[0].passed);
+        assert_eq!(game.obstacles_cleared, 1);
+        assert_eq!(game.score, 11);
+    }
+
+    #[test]
+    fn speed_ramps_up_to_the_cap() {
+        let mut game = GameState::new();
+        game.tick();
+        assert_eq!(game.speed, 1.0 + SPEED_INCREMENT);
+
+        game.speed = MAX_SPEED - SPEED_INCREMENT / 2.0;
+        game.tick();
+        assert_eq!(game.speed, MAX_SPEED);
+        game.tick();
+        assert_eq!(game.speed, MAX_SPEED);
+    }
+
+    proptest! {
+        #[test]
+        fn player_never_below_ground(
+            seed in any::<u64>(),
+            width in 40u16..200,
+            actions in prop::collection::vec(0u8..4, 0..900),
+        ) {
+            let mut game = GameState::new().with_terminal_width(width).with_seed(seed);
+            for action in actions {
+                apply(&mut game, action);
+                game.tick();
+                game.maybe_spawn_obstacle();
+                prop_assert!(game.player.y >= 0.0);
+                if game.player.y == 0.0 {
+                    prop_assert!(game.player.state != PlayerState::Jumping);
+                }
+            }
+        }
+
+        #[test]
+        fn jump_lands_in_bounded_ticks(
+            speed in 1.0f32..MAX_SPEED,
+            actions in prop::collection::vec(0u8..4, 0..40),
+        ) {
+            let mut game = GameState::new();
+            game.speed = speed;
+            game.jump();
+            // Mashing keys mid-air changes nothing
+            for &action in actions.iter().take(max_airtime() as usize) {
+                if game.player.state != PlayerState::Jumping {
+                    break;
+                }
+                apply(&mut game, action);
+                game.tick();
+            }
+            for _ in 0..max_airtime() {
+                if game.player.state != PlayerState::Jumping {
+                    break;
+                }
+                game.tick();
+            }
+            prop_assert_ne!(game.player.state, PlayerState::Jumping);
+            prop_assert_eq!(game.player.y, 0.0);
+        }
+
+        #[test]
+        fn ducking_clears_flying(
+            speed in 1.0f32..MAX_SPEED,
+            distance in 0.0f32..80.0,
+        ) {
+            let run_past = |duck: bool| {
+                let mut game = GameState::new();
+                game.speed = speed;
+                if duck {
+                    game.toggle_duck();
+                }
+                game.obstacles.push(Obstacle::new(game.player.x + distance, ObstacleType::Flying));
+                while game.obstacles.first().is_some_and(|o| !o.passed) {
+                    game.tick();
+                }
+                game
+            };
+
+            let ducked = run_past(true);
+            prop_assert_eq!(ducked.collisions, 0);
+            prop_assert_eq!(ducked.obstacles_cleared, 1);
+            // Standing, the same obstacle hits
+            let stood = run_past(false);
+            prop_assert_eq!(stood.collisions, 1);
+            prop_assert_eq!(stood.obstacles_cleared, 0);
+        }
+
+        #[test]
+        fn cleared_obstacles_are_never_hit(
+            seed in any::<u64>(),
+            actions in prop::collection::vec(0u8..4, 0..900),
+        ) {
+            let mut game = GameState::new().with_seed(seed);
+            for action in actions {
+                apply(&mut game, action);
+                let before: Vec<(bool, bool)> =
+                    game.obstacles.iter().map(|o| (o.passed, o.collided)).collect();
+                let cleared_before = game.obstacles_cleared;
+                game.tick();
+
+                // tick() only drops obstacles off the front
+                let dropped = before.len() - game.obstacles.len();
+                let mut newly_cleared = 0;
+                let after = game.obstacles.iter().zip(&before[dropped..]);
+                for (obstacle, &(was_passed, was_hit)) in after {
+                    // Nothing is hit once it's past the player
+                    prop_assert!(!(was_passed && !was_hit && obstacle.collided));
+                    if !was_passed && obstacle.passed && !obstacle.collided {
+                        newly_cleared += 1;
+                    }
+                }
+                // The bonus goes only to obstacles that got past without a
+                // hit, even when the hit lands on the tick they pass
+                prop_assert_eq!(game.obstacles_cleared - cleared_before, newly_cleared);
+                game.maybe_spawn_obstacle();
+
+                let spawned: u32 = ObstacleType::ALL
+                    .iter()
+                    .map(|&t| game.spawned_by_type.get(t))
+                    .sum();
+                prop_assert!(game.obstacles_cleared + game.collisions <= spawned);
+            }
+        }
+
+        #[test]
+        fn speed_never_exceeds_cap(
+            start in 0.5f32..=MAX_SPEED,
+            ticks in 0u32..5000,
+        ) {
+            let mut game = GameState::new();
+            game.speed = start;
+            for _ in 0..ticks {
+                game.tick();
+                prop_assert!(game.speed <= MAX_SPEED);
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Small seedable PRNG (SplitMix64)
+/// Every random decision in game logic goes through this, so a seed
+/// fully determines a run
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// The same seed always gives the same sequence
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// A fresh seed for when the player didn't pick one
+    /// Kept to 32 bits so it's easy to read off the end screen and retype
+    pub fn random_seed() -> u64 {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        Rng::new(nanos ^ u64::from(std::process::id())).next_u64() >> 32
+    }
+
+    /// Next number in the sequence
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `low..high`
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        debug_assert!(low < high);
+        low + self.next_u64() % (high - low)
+    }
+
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f32) -> bool {
+        // Top 24 bits fit an f32 exactly
+        let roll = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        roll < p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+        let from_a: Vec<u64> = (0..16).map(|_| a.next_u64()).collect();
+        let from_b: Vec<u64> = (0..16).map(|_| b.next_u64()).collect();
+        let from_c: Vec<u64> = (0..16).map(|_| c.next_u64()).collect();
+        assert_eq!(from_a, from_b);
+        assert_ne!(from_a, from_c);
+    }
+
+    #[test]
+    fn range_stays_in_bounds_and_covers_it() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let n = rng.range(10, 15);
+            assert!((10..15).contains(&n));
+            seen[(n - 10) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn chance_matches_its_probability() {
+        let mut rng = Rng::new(3);
+        assert!(!(0..1000).any(|_| rng.chance(0.0)));
+        assert!((0..1000).all(|_| rng.chance(1.0)));
+        let hits = (0..10_000).filter(|_| rng.chance(0.25)).count();
+        assert!((2300..2700).contains(&hits), "{hits}");
+    }
+
+    #[test]
+    fn random_seed_fits_in_32_bits() {
+        assert!(Rng::random_seed() <= u64::from(u32::MAX));
+    }
+}
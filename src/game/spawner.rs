@@ -15,6 +15,10 @@ const FLYING_THRESHOLD: u32 = 900;
 impl GameState {
     /// Possibly spawn a new obstacle
     pub fn maybe_spawn_obstacle(&mut self) {
+        if self.is_frozen() {
+            return;
+        }
+
         // Don't spawn if there are obstacles too close to the right edge
         let spawn_x = self.terminal_width as f32 + 10.0;
 
@@ -26,20 +30,27 @@ impl GameState {
             }
         }
 
-        // Random spawn check based on frame count
-        // Use a simple pseudo-random based on frame count and score
-        let spawn_seed = self.frame_count.wrapping_mul(31337).wrapping_add(self.score as u64);
-        let spawn_interval = MIN_SPAWN_INTERVAL + (spawn_seed % (MAX_SPAWN_INTERVAL - MIN_SPAWN_INTERVAL));
+        // Schedule the first obstacle on the first call
+        if self.next_spawn_frame == 0 {
+            self.next_spawn_frame = self.frame_count + self.spawn_interval();
+        }
 
-        if self.frame_count % spawn_interval == 0 {
-            let obstacle_type = self.pick_obstacle_type(spawn_seed);
+        if self.frame_count >= self.next_spawn_frame {
+            let obstacle_type = self.pick_obstacle_type();
             self.obstacles.push(Obstacle::new(spawn_x, obstacle_type));
+            self.spawned_by_type.add(obstacle_type);
+            self.next_spawn_frame = self.frame_count + self.spawn_interval();
         }
     }
 
+    /// Random gap until the next obstacle
+    fn spawn_interval(&mut self) -> u64 {
+        self.rng.range(MIN_SPAWN_INTERVAL, MAX_SPAWN_INTERVAL)
+    }
+
     /// Pick obstacle type based on score (gradual introduction)
-    fn pick_obstacle_type(&self, seed: u64) -> ObstacleType {
-        let type_seed = seed.wrapping_mul(7919);
+    fn pick_obstacle_type(&mut self) -> ObstacleType {
+        let roll = self.rng.range(0, 10);
 
         // Build available types based on score
         if self.score < TALL_THRESHOLD {
@@ -47,20 +58,20 @@ impl GameState {
             ObstacleType::Small
         } else if self.score < DOUBLE_THRESHOLD {
             // Mid game: small + tall
-            match type_seed % 10 {
+            match roll {
                 0..=5 => ObstacleType::Small,  // 60% small
                 _ => ObstacleType::Tall,       // 40% tall
             }
         } else if self.score < FLYING_THRESHOLD {
             // Later game: small + tall + double
-            match type_seed % 10 {
+            match roll {
                 0..=4 => ObstacleType::Small,  // 50% small
                 5..=7 => ObstacleType::Tall,   // 30% tall
                 _ => ObstacleType::Double,     // 20% double
             }
         } else {
             // Full game: all types including flying
-            match type_seed % 10 {
+            match roll {
                 0..=3 => ObstacleType::Small,  // 40% small
                 4..=5 => ObstacleType::Tall,   // 20% tall
                 6..=7 => ObstacleType::Double, // 20% double
@@ -1,18 +1,33 @@
+use serde::{Deserialize, Serialize};
+
 use crate::render::sprites::{ClaudeSprite, ObstacleType};
+use super::rng::Rng;
 
+/// What Clawd is doing, which decides its sprite and hitbox
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum PlayerState {
+    /// On the ground, feet moving
     Running,
+    /// In the air, rising or falling
     Jumping,
+    /// Crouched low enough to pass under flying obstacles
     Ducking,
-    Landing(u8), // Frames remaining in landing squash
+    /// Squashed after touching down, with the frames remaining
+    Landing(u8),
 }
 
+/// Clawd: fixed x, with y measured up from the ground
 #[derive(Debug)]
 pub struct Player {
+    /// Column of the sprite's left edge
     pub x: f32,
+    /// Height above the ground, in rows
     pub y: f32,
+    /// y before the last tick, for interpolated drawing
+    pub prev_y: f32,
+    /// Rows per tick, positive going up
     pub velocity_y: f32,
+    /// What Clawd is doing
     pub state: PlayerState,
 }
 
@@ -21,6 +36,7 @@ impl Default for Player {
         Self {
             x: 8.0, // Fixed position from left edge
             y: 0.0, // Ground level
+            prev_y: 0.0,
             velocity_y: 0.0,
             state: PlayerState::Running,
         }
@@ -33,48 +49,171 @@ impl Player {
         ClaudeSprite::WIDTH as f32 - 2.0
     }
 
+    /// Hitbox height in the current state
     pub fn hitbox_height(&self) -> f32 {
-        match self.state {
+        Self::hitbox_height_when(self.state)
+    }
+
+    /// Hitbox height the player would have in `state`
+    /// Inset half a row on top, so a flying obstacle's bottom row still
+    /// catches a standing head but clears a ducked one
+    pub fn hitbox_height_when(state: PlayerState) -> f32 {
+        match state {
             PlayerState::Ducking => ClaudeSprite::DUCK_HEIGHT as f32 - 0.5,
             PlayerState::Landing(_) => ClaudeSprite::HEIGHT as f32 - 1.0,
-            _ => ClaudeSprite::HEIGHT as f32 - 1.0,
+            _ => ClaudeSprite::HEIGHT as f32 - 0.5,
         }
     }
 }
 
+/// An obstacle scrolling in from the right
 #[derive(Debug)]
 pub struct Obstacle {
+    /// Column of the sprite's left edge
     pub x: f32,
+    /// x before the last tick, for interpolated drawing
+    pub prev_x: f32,
+    /// Which sprite and hitbox it has
     pub obstacle_type: ObstacleType,
-    pub passed: bool,    // For scoring when player clears it
-    pub collided: bool,  // No bonus if player hit this one
+    /// Has scrolled past the player, for scoring when they clear it
+    pub passed: bool,
+    /// The player hit it, so it earns no bonus
+    pub collided: bool,
+    /// The player was ducking while it went past
+    pub ducked: bool,
 }
 
 impl Obstacle {
+    /// An obstacle at column `x` that hasn't reached the player yet
     pub fn new(x: f32, obstacle_type: ObstacleType) -> Self {
         Self {
             x,
+            prev_x: x,
             obstacle_type,
             passed: false,
             collided: false,
+            ducked: false,
         }
     }
 }
 
+/// A tally per obstacle type
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TypeCounts([u32; ObstacleType::ALL.len()]);
+
+impl TypeCounts {
+    /// Count one more of `obstacle_type`
+    pub fn add(&mut self, obstacle_type: ObstacleType) {
+        self.0[obstacle_type as usize] += 1;
+    }
+
+    /// How many of `obstacle_type` were counted
+    pub fn get(&self, obstacle_type: ObstacleType) -> u32 {
+        self.0[obstacle_type as usize]
+    }
+}
+
+/// Something that happened during the last tick, for listeners such as
+/// achievements
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameEvent {
+    /// An obstacle went past without a hit
+    Cleared {
+        /// What went past
+        obstacle_type: ObstacleType,
+        /// The player was ducking as it did
+        ducked: bool,
+    },
+    /// The player hit an obstacle for the first time
+    Collision(ObstacleType),
+}
+
+/// Why the run ended
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndReason {
+    /// Every session the run was waiting on finished compacting
+    Compaction,
+    /// The Claude Code process went away
+    ClaudeExited,
+    /// The run hit its safety timeout
+    Timeout,
+    /// The player quit
+    Quit,
+}
+
+/// Whether the run is still going
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GamePhase {
+    /// The run is on
+    Playing,
+    /// Run is over; the end banner shows until frames_left runs out.
+    /// With keep_playing the world keeps moving until the player presses a key.
+    Ended {
+        /// Why it ended
+        reason: EndReason,
+        /// Ticks the end banner has left
+        frames_left: u16,
+        /// The world keeps moving behind the banner
+        keep_playing: bool,
+    },
+}
+
+/// Everything about a run
+/// Advance it with `tick()` and `maybe_spawn_obstacle()`, steer it with
+/// `jump()` and `toggle_duck()`, and draw it with `GameScene`
 #[derive(Debug)]
 pub struct GameState {
+    /// The player character
     pub player: Player,
+    /// Obstacles on screen, oldest first
     pub obstacles: Vec<Obstacle>,
+    /// Points so far, including any scored in demo mode
     pub score: u32,
+    /// Ticks since the run started
     pub frame_count: u64,
+    /// How far the ground has scrolled, in columns
     pub scroll_offset: u32,
+    /// Scroll speed multiplier, rising as the run goes on
     pub speed: f32,
+    /// The run should stop and the game exit
     pub should_quit: bool,
-    pub collision_flash: u8,  // Frames remaining for collision flash effect
-    pub milestone_flash: u8,  // Frames remaining for milestone celebration
-    pub last_milestone: u32,  // Last milestone hit (100, 500, 1000, etc)
-    pub score_pop: u8,        // Frames remaining for +10 bonus flash
+    /// Frames remaining for collision flash effect
+    pub collision_flash: u8,
+    /// Frames remaining for milestone celebration
+    pub milestone_flash: u8,
+    /// Last milestone hit (100, 500, 1000, etc)
+    pub last_milestone: u32,
+    /// Frames remaining for +10 bonus flash
+    pub score_pop: u8,
+    /// Message for the toast, e.g. an achievement
+    pub toast: Option<String>,
+    /// Frames remaining for the toast
+    pub toast_flash: u8,
+    /// Obstacles hit this run
+    pub collisions: u32,
+    /// Obstacles that went past without a hit
+    pub obstacles_cleared: u32,
+    /// Autopilot has the controls
+    pub demo: bool,
+    /// Points scored while it did
+    pub demo_score: u32,
+    /// Obstacles spawned this run, by type
+    pub spawned_by_type: TypeCounts,
+    /// Obstacles hit this run, by type
+    pub collisions_by_type: TypeCounts,
+    /// What happened during the last tick
+    pub events: Vec<GameEvent>,
+    /// Whether the run is still going
+    pub phase: GamePhase,
+    /// Width of the play area; obstacles spawn at its right edge
     pub terminal_width: u16,
+    /// Seed the obstacle sequence came from
+    pub seed: u64,
+    /// Obstacle generator, seeded from `seed`
+    pub rng: Rng,
+    /// Tick of the next spawn, 0 until the first obstacle is scheduled
+    pub next_spawn_frame: u64,
 }
 
 impl Default for GameState {
@@ -91,18 +230,90 @@ impl Default for GameState {
             milestone_flash: 0,
             last_milestone: 0,
             score_pop: 0,
+            toast: None,
+            toast_flash: 0,
+            collisions: 0,
+            obstacles_cleared: 0,
+            demo: false,
+            demo_score: 0,
+            spawned_by_type: TypeCounts::default(),
+            collisions_by_type: TypeCounts::default(),
+            events: Vec::new(),
+            phase: GamePhase::Playing,
             terminal_width: 80,
+            seed: 0,
+            rng: Rng::new(0),
+            next_spawn_frame: 0,
         }
     }
 }
 
 impl GameState {
+    /// A fresh run with seed 0 on an 80-column screen
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Play area width, which decides where obstacles spawn
     pub fn with_terminal_width(mut self, width: u16) -> Self {
         self.terminal_width = width;
         self
     }
+
+    /// Same seed, same obstacles
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = Rng::new(seed);
+        self
+    }
+}
+
+impl GameState {
+    /// End the run and show the end banner for `grace_frames`
+    pub fn finish(&mut self, reason: EndReason, grace_frames: u16, keep_playing: bool) {
+        if self.phase == GamePhase::Playing {
+            self.phase = GamePhase::Ended {
+                reason,
+                frames_left: grace_frames,
+                keep_playing,
+            };
+        }
+    }
+
+    /// True once the run has ended, even while the end banner is up
+    pub fn is_ended(&self) -> bool {
+        matches!(self.phase, GamePhase::Ended { .. })
+    }
+
+    /// Score earned by the player, leaving out demo periods
+    pub fn player_score(&self) -> u32 {
+        self.score - self.demo_score
+    }
+
+    /// Why the run ended, None while it's still going
+    pub fn end_reason(&self) -> Option<EndReason> {
+        match self.phase {
+            GamePhase::Ended { reason, .. } => Some(reason),
+            GamePhase::Playing => None,
+        }
+    }
+
+    /// Show `message` in the play area for `frames` ticks
+    pub fn show_toast(&mut self, message: impl Into<String>, frames: u8) {
+        self.toast = Some(message.into());
+        self.toast_flash = frames;
+    }
+
+    /// World stops moving while the end banner is up
+    pub fn is_frozen(&self) -> bool {
+        matches!(self.phase, GamePhase::Ended { keep_playing: false, .. })
+    }
+
+    /// The banner has been shown for its full grace period
+    pub fn end_screen_done(&self) -> bool {
+        matches!(
+            self.phase,
+            GamePhase::Ended { frames_left: 0, keep_playing: false, .. }
+        )
+    }
 }
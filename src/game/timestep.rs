@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use super::physics::TICKS_PER_SECOND;
+
+/// Most ticks run to catch up in one go; time beyond that is dropped so a
+/// long stall doesn't turn into a burst of unplayable frames
+pub const MAX_CATCH_UP_TICKS: u32 = 5;
+
+/// Turns elapsed time into a whole number of game ticks
+/// Game speed stays the same however often (or unevenly) the caller draws
+#[derive(Debug)]
+pub struct FixedTimestep {
+    step: Duration,
+    max_ticks: u32,
+    accumulator: Duration, // Time owed that hasn't made up a whole tick yet
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FixedTimestep {
+    /// Real-time game ticks
+    pub fn new() -> Self {
+        Self::with_rate(1.0)
+    }
+
+    /// Ticks `rate` times faster than real time, with the catch-up cap
+    /// scaled to match
+    pub fn with_rate(rate: f32) -> Self {
+        let step = Duration::from_secs(1).div_f32(TICKS_PER_SECOND as f32 * rate);
+        Self {
+            step,
+            max_ticks: (MAX_CATCH_UP_TICKS as f32 * rate).ceil() as u32,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// Ticks due now that `elapsed` more time has passed
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+
+        let mut ticks = 0;
+        while self.accumulator >= self.step {
+            self.accumulator -= self.step;
+            ticks += 1;
+            if ticks == self.max_ticks {
+                self.accumulator = Duration::ZERO;
+                break;
+            }
+        }
+        ticks
+    }
+
+    /// How far we are between the last tick and the next, 0.0 to 1.0
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator.as_secs_f32() / self.step.as_secs_f32()).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step() -> Duration {
+        Duration::from_secs(1) / TICKS_PER_SECOND as u32
+    }
+
+    #[test]
+    fn remainder_carries_to_the_next_frame() {
+        let mut clock = FixedTimestep::new();
+        assert_eq!(clock.advance(step() * 3 / 4), 0);
+        assert!(clock.alpha() > 0.7);
+        // Two partial frames add up to one tick, with a quarter left over
+        assert_eq!(clock.advance(step() / 2), 1);
+        assert!((clock.alpha() - 0.25).abs() < 0.01);
+        assert_eq!(clock.advance(step() * 2), 2);
+    }
+
+    #[test]
+    fn catch_up_is_capped_after_a_stall() {
+        let mut clock = FixedTimestep::new();
+        assert_eq!(clock.advance(Duration::from_secs(2)), MAX_CATCH_UP_TICKS);
+        // The rest of the stall is dropped rather than owed
+        assert_eq!(clock.alpha(), 0.0);
+        assert_eq!(clock.advance(step() / 2), 0);
+
+        let mut fast = FixedTimestep::with_rate(4.0);
+        assert_eq!(fast.advance(Duration::from_secs(2)), MAX_CATCH_UP_TICKS * 4);
+    }
+
+    #[test]
+    fn alpha_stays_below_one() {
+        let mut clock = FixedTimestep::new();
+        for ms in [1, 7, 33, 34, 50, 90, 250, 16, 3, 1000] {
+            clock.advance(Duration::from_millis(ms));
+            let alpha = clock.alpha();
+            assert!((0.0..1.0).contains(&alpha), "alpha {alpha} after {ms}ms");
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, IsTerminal, Read};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Hook event that compaction runs under
+pub const PRE_COMPACT: &str = "PreCompact";
+
+/// Hook event fired when a session resumes after compaction
+pub const SESSION_START: &str = "SessionStart";
+
+/// SessionStart matcher for the resume that follows compaction
+pub const COMPACT_SOURCE: &str = "compact";
+
+/// Default game length when Claude compacts on its own (seconds)
+const AUTO_DURATION: u64 = 300;
+
+/// Default game length for `/compact` (seconds)
+/// Manual compaction usually happens well before the context is full,
+/// so there's less to summarize
+const MANUAL_DURATION: u64 = 120;
+
+/// What started the compaction
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactTrigger {
+    /// Context window filled up
+    Auto,
+    /// User ran `/compact`
+    Manual,
+}
+
+impl CompactTrigger {
+    /// Safety-net timeout when `--duration` isn't given
+    pub fn default_duration(&self) -> u64 {
+        match self {
+            CompactTrigger::Auto => AUTO_DURATION,
+            CompactTrigger::Manual => MANUAL_DURATION,
+        }
+    }
+}
+
+/// Hook input JSON from Claude Code
+/// Fields shared by every hook event plus the PreCompact-specific ones
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct HookInput {
+    /// Claude Code session the hook fired for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// The session's transcript, possibly starting with `~`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript_path: Option<PathBuf>,
+    /// Directory Claude Code is running in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// Claude Code's permission mode, passed through untouched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_mode: Option<String>,
+    /// Which hook event this is, e.g. `PreCompact`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hook_event_name: Option<String>,
+    /// Whether compaction is automatic or from `/compact`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<CompactTrigger>,
+    /// Text passed to `/compact`, empty for auto compaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_instructions: Option<String>,
+}
+
+impl HookInput {
+    /// True unless the payload names some other hook event
+    /// A payload without `hook_event_name` is taken to be PreCompact.
+    pub fn is_compaction(&self) -> bool {
+        self.hook_event_name
+            .as_deref()
+            .is_none_or(|name| name == PRE_COMPACT)
+    }
+
+    /// Check the fields a PreCompact payload must carry
+    /// Only payloads that name the event are held to this.
+    pub fn validate(&self) -> Result<(), HookInputError> {
+        if self.hook_event_name.as_deref() != Some(PRE_COMPACT) {
+            return Ok(());
+        }
+        if self.transcript_path.is_none() {
+            return Err(HookInputError::Invalid(
+                "PreCompact payload is missing transcript_path",
+            ));
+        }
+        if self.trigger.is_none() {
+            return Err(HookInputError::Invalid(
+                "PreCompact payload is missing trigger",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Transcript path with a leading `~` expanded
+    pub fn transcript(&self) -> Option<PathBuf> {
+        let path = self.transcript_path.as_ref()?;
+        match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+            (Ok(rest), Some(home)) => Some(PathBuf::from(home).join(rest)),
+            _ => Some(path.clone()),
+        }
+    }
+}
+
+/// Why the hook payload couldn't be read
+#[derive(Debug)]
+pub enum HookInputError {
+    /// Reading stdin failed
+    Io(io::Error),
+    /// Stdin is a pipe but nothing arrived (or it never closed) in time
+    Timeout(Duration),
+    /// Not valid hook JSON
+    Malformed(serde_json::Error),
+    /// Parsed, but missing something the event requires
+    Invalid(&'static str),
+}
+
+impl fmt::Display for HookInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookInputError::Io(err) => write!(f, "failed to read hook input from stdin: {err}"),
+            HookInputError::Timeout(timeout) => write!(
+                f,
+                "no hook input on stdin after {}ms (pass --transcript or --demo when running by hand)",
+                timeout.as_millis()
+            ),
+            HookInputError::Malformed(err) => write!(f, "malformed hook input: {err}"),
+            HookInputError::Invalid(reason) => write!(f, "invalid hook input: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for HookInputError {}
+
+/// Read the hook payload Claude Code pipes to stdin
+/// Returns None when stdin is a terminal (launched by hand) or empty.
+/// The read happens on a helper thread so a pipe that never closes
+/// can't hang us before the game starts.
+pub fn read_from_stdin(timeout: Duration) -> Result<Option<HookInput>, HookInputError> {
+    if io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut input = String::new();
+        let result = io::stdin().read_to_string(&mut input).map(|_| input);
+        let _ = tx.send(result);
+    });
+
+    let input = match rx.recv_timeout(timeout) {
+        Ok(result) => result.map_err(HookInputError::Io)?,
+        Err(_) => return Err(HookInputError::Timeout(timeout)),
+    };
+
+    if input.trim().is_empty() {
+        return Ok(None);
+    }
+    parse(&input).map(Some)
+}
+
+/// Parse and validate a hook payload
+pub fn parse(input: &str) -> Result<HookInput, HookInputError> {
+    let hook_input: HookInput = serde_json::from_str(input).map_err(HookInputError::Malformed)?;
+    hook_input.validate()?;
+    Ok(hook_input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_event_name_counts_as_compaction() {
+        let input = parse(r#"{"session_id":"abc"}"#).unwrap();
+        assert!(input.is_compaction());
+
+        let input = parse(r#"{"hook_event_name":"Stop"}"#).unwrap();
+        assert!(!input.is_compaction());
+
+        assert!(parse(r#"{"hook_event_name":"PreCompact"}"#).is_err());
+    }
+}
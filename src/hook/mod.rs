@@ -0,0 +1,10 @@
+/// The JSON payload Claude Code passes to hooks
+pub mod input;
+/// Adding and removing our hooks in settings.json
+pub mod settings;
+
+pub use input::{
+    COMPACT_SOURCE, CompactTrigger, HookInput, HookInputError, PRE_COMPACT, SESSION_START,
+    read_from_stdin,
+};
+pub use settings::{Scope, SettingsFile};
@@ -0,0 +1,400 @@
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+use similar::TextDiff;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::input::{COMPACT_SOURCE, CompactTrigger, PRE_COMPACT, SESSION_START};
+
+/// Key we set on hook entries we installed, so we can find them again
+/// whatever command they run
+const MARKER: &str = "clawd-runner";
+
+/// Which Claude Code settings file to edit
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Scope {
+    /// ~/.claude/settings.json
+    User,
+    /// .claude/settings.json in the current directory (checked in)
+    Project,
+    /// .claude/settings.local.json in the current directory (not checked in)
+    Local,
+}
+
+impl Scope {
+    /// The settings file this scope edits
+    pub fn path(&self) -> io::Result<PathBuf> {
+        match self {
+            Scope::User => {
+                let home = std::env::var_os("HOME")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME is not set"))?;
+                Ok(PathBuf::from(home).join(".claude").join("settings.json"))
+            }
+            Scope::Project => Ok(PathBuf::from(".claude").join("settings.json")),
+            Scope::Local => Ok(PathBuf::from(".claude").join("settings.local.json")),
+        }
+    }
+}
+
+/// A settings.json loaded for editing
+/// Keeps the original text to diff against and the file's indent unit
+/// and trailing newline for the rewrite. The rewrite re-serializes the
+/// whole file, so any other formatting is normalized.
+#[derive(Debug)]
+pub struct SettingsFile {
+    path: PathBuf,
+    original: String,
+    settings: Value,
+    indent: String,
+    trailing_newline: bool,
+}
+
+impl SettingsFile {
+    /// Load a settings file, treating a missing one as empty
+    /// Only the indent unit and trailing newline survive a rewrite; other
+    /// formatting doesn't.
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let original = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let settings = if original.trim().is_empty() {
+            Value::Object(Map::new())
+        } else {
+            serde_json::from_str(&original).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} is not valid JSON: {err}", path.display()),
+                )
+            })?
+        };
+        if !settings.is_object() {
+            return Err(invalid(&path, "top level is not an object"));
+        }
+
+        // First indented line tells us the file's indent unit
+        let indent = original
+            .lines()
+            .map(|line| &line[..line.len() - line.trim_start().len()])
+            .find(|ws| !ws.is_empty())
+            .unwrap_or("  ")
+            .to_string();
+        let trailing_newline = original.is_empty() || original.ends_with('\n');
+
+        Ok(Self {
+            path,
+            original,
+            settings,
+            indent,
+            trailing_newline,
+        })
+    }
+
+    /// Where the file lives
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// True if any hook entry was installed by us
+    pub fn has_our_hook(&self) -> bool {
+        let Some(hooks) = self.settings.get("hooks").and_then(Value::as_object) else {
+            return false;
+        };
+        hooks
+            .values()
+            .filter_map(Value::as_array)
+            .flatten()
+            .filter_map(|group| group.get("hooks").and_then(Value::as_array))
+            .flatten()
+            .any(is_ours)
+    }
+
+    /// Append a hook group for `event` running `command`
+    /// Existing groups for the event are left alone
+    pub fn add_hook(
+        &mut self,
+        event: &str,
+        matcher: Option<&str>,
+        command: &str,
+    ) -> io::Result<()> {
+        let path = self.path.clone();
+        let root = self.settings.as_object_mut().expect("checked in load");
+        let hooks = root
+            .entry("hooks")
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| invalid(&path, "\"hooks\" is not an object"))?;
+        let groups = hooks
+            .entry(event)
+            .or_insert_with(|| Value::Array(Vec::new()))
+            .as_array_mut()
+            .ok_or_else(|| invalid(&path, &format!("\"hooks.{event}\" is not an array")))?;
+
+        let mut group = Map::new();
+        if let Some(matcher) = matcher {
+            group.insert("matcher".into(), matcher.into());
+        }
+        let mut hook = Map::new();
+        hook.insert("type".into(), "command".into());
+        hook.insert("command".into(), command.into());
+        hook.insert(MARKER.into(), true.into());
+        group.insert("hooks".into(), Value::Array(vec![Value::Object(hook)]));
+        groups.push(Value::Object(group));
+        Ok(())
+    }
+
+    /// Add the PreCompact hook that runs `command` and the SessionStart
+    /// hook that tells the game compaction is over
+    pub fn add_our_hooks(
+        &mut self,
+        trigger: Option<CompactTrigger>,
+        command: &str,
+        exe: &Path,
+    ) -> io::Result<()> {
+        self.add_hook(PRE_COMPACT, matcher_for(trigger), command)?;
+        self.add_hook(SESSION_START, Some(COMPACT_SOURCE), &signal_command(exe))
+    }
+
+    /// Remove every hook entry we installed
+    /// Groups, events and the `hooks` object are dropped only if removing
+    /// our entries left them empty. Returns how many commands were removed.
+    pub fn remove_our_hooks(&mut self) -> usize {
+        let root = self.settings.as_object_mut().expect("checked in load");
+        let Some(hooks) = root.get_mut("hooks").and_then(Value::as_object_mut) else {
+            return 0;
+        };
+
+        let mut removed = 0;
+        for groups in hooks.values_mut().filter_map(Value::as_array_mut) {
+            groups.retain_mut(|group| {
+                let Some(commands) = group.get_mut("hooks").and_then(Value::as_array_mut) else {
+                    return true;
+                };
+                let before = commands.len();
+                commands.retain(|hook| !is_ours(hook));
+                let ours = before - commands.len();
+                removed += ours;
+                ours == 0 || !commands.is_empty()
+            });
+        }
+
+        if removed > 0 {
+            hooks.retain(|_, groups| groups.as_array().is_none_or(|g| !g.is_empty()));
+            if hooks.is_empty() {
+                root.remove("hooks");
+            }
+        }
+        removed
+    }
+
+    /// Serialize the settings using the file's own indentation
+    pub fn render(&self) -> io::Result<String> {
+        let mut out = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(self.indent.as_bytes());
+        let mut ser = serde_json::Serializer::with_formatter(&mut out, formatter);
+        serde::Serialize::serialize(&self.settings, &mut ser)?;
+        let mut text = String::from_utf8(out).expect("serde_json writes UTF-8");
+        if self.trailing_newline {
+            text.push('\n');
+        }
+        Ok(text)
+    }
+
+    /// Unified diff between the file on disk and what we'd write
+    pub fn diff(&self) -> io::Result<String> {
+        let updated = self.render()?;
+        let name = self.path.display().to_string();
+        Ok(TextDiff::from_lines(&self.original, &updated)
+            .unified_diff()
+            .header(&name, &name)
+            .to_string())
+    }
+
+    /// Write the settings back, keeping a `.bak` copy of the old file
+    /// Returns the backup path if there was a file to back up
+    pub fn save(&self) -> io::Result<Option<PathBuf>> {
+        let text = self.render()?;
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+
+        let backup = if self.path.exists() {
+            let backup = with_suffix(&self.path, ".bak");
+            fs::copy(&self.path, &backup)?;
+            Some(backup)
+        } else {
+            None
+        };
+
+        // Write-then-rename so Claude Code never sees a half-written file
+        let tmp = with_suffix(&self.path, ".tmp");
+        fs::write(&tmp, text)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(backup)
+    }
+}
+
+/// The hook command to install when the user doesn't supply one
+pub fn default_command(exe: &Path) -> String {
+    if cfg!(target_os = "macos") {
+        // Hooks have no terminal, so open one. Spaces are escaped for the
+        // shell Terminal runs the command in.
+        let exe = exe.display().to_string().replace(' ', "\\\\ ");
+        format!("osascript -e 'tell app \"Terminal\" to do script \"{exe}\"'")
+    } else {
+        shell_quote(&exe.display().to_string())
+    }
+}
+
+/// Hook command that tells a running game compaction is over
+pub fn signal_command(exe: &Path) -> String {
+    format!("{} signal done", shell_quote(&exe.display().to_string()))
+}
+
+/// The PreCompact matcher for a trigger filter (None matches both)
+pub fn matcher_for(trigger: Option<CompactTrigger>) -> Option<&'static str> {
+    trigger.map(|t| match t {
+        CompactTrigger::Auto => "auto",
+        CompactTrigger::Manual => "manual",
+    })
+}
+
+/// Quote a string for POSIX sh if it contains anything special
+pub fn shell_quote(s: &str) -> String {
+    let safe = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-+=:,@%".contains(c));
+    if safe {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+fn is_ours(hook: &Value) -> bool {
+    hook.get(MARKER).and_then(Value::as_bool) == Some(true)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn invalid(path: &Path, reason: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {reason}", path.display()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A settings file in a temp dir of its own, removed when dropped
+    struct TempSettings(PathBuf);
+
+    impl TempSettings {
+        fn new(name: &str, contents: &str) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("clawd-runner-test-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("settings.json");
+            fs::write(&path, contents).unwrap();
+            Self(path)
+        }
+
+        fn load(&self) -> io::Result<SettingsFile> {
+            SettingsFile::load(self.0.clone())
+        }
+    }
+
+    impl Drop for TempSettings {
+        fn drop(&mut self) {
+            if let Some(dir) = self.0.parent() {
+                let _ = fs::remove_dir_all(dir);
+            }
+        }
+    }
+
+    #[test]
+    fn indentation_is_kept() {
+        let file = TempSettings::new("indent", "{\n\t\"model\": \"opus\"\n}");
+        let mut settings = file.load().unwrap();
+        assert_eq!(settings.indent, "\t");
+        settings.add_hook("PreCompact", None, "run").unwrap();
+        let text = settings.render().unwrap();
+        assert!(text.starts_with("{\n\t\"model\": \"opus\",\n\t\"hooks\": {\n\t\t\"PreCompact\""));
+        assert!(!text.ends_with('\n'));
+    }
+
+    #[test]
+    fn save_backs_up_the_old_file() {
+        let original = "{\n    \"model\": \"opus\"\n}\n";
+        let file = TempSettings::new("backup", original);
+        let mut settings = file.load().unwrap();
+        settings.add_hook("PreCompact", None, "run").unwrap();
+
+        let backup = settings.save().unwrap().unwrap();
+        assert_eq!(backup, with_suffix(&file.0, ".bak"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), original);
+        assert!(file.load().unwrap().has_our_hook());
+    }
+
+    #[test]
+    fn dry_run_diff_shows_the_change() {
+        let file = TempSettings::new("diff", "{\n  \"model\": \"opus\"\n}\n");
+        let mut settings = file.load().unwrap();
+        settings
+            .add_hook("PreCompact", Some("auto"), "run")
+            .unwrap();
+
+        let diff = settings.diff().unwrap();
+        let name = file.0.display().to_string();
+        assert!(diff.starts_with(&format!("--- {name}\n+++ {name}\n")));
+        assert!(diff.contains("\n {\n-  \"model\": \"opus\"\n+  \"model\": \"opus\",\n"));
+        assert!(diff.contains("\n+            \"command\": \"run\",\n"));
+        // Nothing is written
+        assert!(!file.load().unwrap().has_our_hook());
+    }
+
+    #[test]
+    fn unexpected_hook_values_are_errors() {
+        for contents in [r#"{"hooks": []}"#, r#"{"hooks": {"PreCompact": {}}}"#] {
+            let file = TempSettings::new("unexpected", contents);
+            let mut settings = file.load().unwrap();
+            assert!(!settings.has_our_hook());
+            let err = settings
+                .add_our_hooks(None, "run", Path::new("/bin/clawd-runner"))
+                .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            // Left as it was, so there's nothing for a save to clobber
+            let value: Value = serde_json::from_str(contents).unwrap();
+            assert_eq!(settings.settings, value);
+        }
+    }
+
+    #[test]
+    fn custom_commands_are_found_and_removed() {
+        let path = std::env::temp_dir().join("clawd-runner-missing-settings.json");
+        let mut settings = SettingsFile::load(path).unwrap();
+        settings.add_hook("PreCompact", None, "echo other").unwrap();
+        settings.settings["hooks"]["PreCompact"][0]["hooks"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove(MARKER);
+        assert!(!settings.has_our_hook());
+
+        settings.add_hook("PreCompact", None, "~/bin/wrap").unwrap();
+        assert!(settings.has_our_hook());
+        assert_eq!(settings.remove_our_hooks(), 1);
+        assert!(!settings.has_our_hook());
+        let groups = settings.settings["hooks"]["PreCompact"].as_array().unwrap();
+        assert_eq!(groups.len(), 1);
+    }
+}
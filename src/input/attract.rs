@@ -0,0 +1,139 @@
+use super::{Autopilot, GameAction, Policy};
+use crate::game::GameState;
+use crate::game::physics::TICKS_PER_SECOND;
+
+/// How well the attract-mode bot plays: good, but visibly not perfect
+const ATTRACT_SKILL: f32 = 0.85;
+
+/// Hands the game to the autopilot once the player has looked away,
+/// and back again on the next keypress
+#[derive(Debug)]
+pub struct AttractMode {
+    idle_frames: u64, // 0 disables attract mode
+    last_input: u64,  // Frame of the last keypress
+    seed: u64,
+    bot: Option<Autopilot>,
+}
+
+impl AttractMode {
+    /// Take over after `idle_secs` without a key, or never if it's 0
+    pub fn new(idle_secs: u64, seed: u64) -> Self {
+        Self {
+            idle_frames: idle_secs * TICKS_PER_SECOND,
+            last_input: 0,
+            seed,
+            bot: None,
+        }
+    }
+
+    /// True while the autopilot has the controls
+    pub fn is_active(&self) -> bool {
+        self.bot.is_some()
+    }
+
+    /// The player pressed something: they have the controls again
+    pub fn on_key(&mut self, game: &mut GameState) {
+        self.last_input = game.frame_count;
+        if self.bot.take().is_some() {
+            game.demo = false;
+        }
+    }
+
+    /// A tick went by with no key: the bot's move once idle long enough
+    pub fn act(&mut self, game: &mut GameState) -> GameAction {
+        let idle = game.frame_count.saturating_sub(self.last_input);
+        if self.bot.is_none() && self.idle_frames > 0 && idle >= self.idle_frames {
+            self.bot = Some(Autopilot::new(ATTRACT_SKILL, self.seed ^ game.frame_count));
+            game.demo = true;
+        }
+        match self.bot {
+            Some(ref mut bot) => bot.act(game),
+            None => GameAction::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::EndReason;
+    use crate::runner::Runner;
+    use crate::storage::RunRecord;
+    use std::time::{Duration, SystemTime};
+
+    /// Let `ticks` go by without a key, doing whatever the bot says
+    fn idle(attract: &mut AttractMode, game: &mut GameState, ticks: u64) {
+        for _ in 0..ticks {
+            match attract.act(game) {
+                GameAction::Jump => game.jump(),
+                GameAction::ToggleDuck => game.toggle_duck(),
+                _ => {}
+            }
+            game.tick();
+            game.maybe_spawn_obstacle();
+        }
+    }
+
+    #[test]
+    fn takes_over_once_idle() {
+        let mut game = GameState::new().with_seed(7);
+        let mut attract = AttractMode::new(1, 7);
+        idle(&mut attract, &mut game, TICKS_PER_SECOND);
+        assert!(!attract.is_active());
+        assert!(!game.demo);
+
+        idle(&mut attract, &mut game, 1);
+        assert!(attract.is_active());
+        assert!(game.demo);
+    }
+
+    #[test]
+    fn zero_never_takes_over() {
+        let mut game = GameState::new();
+        let mut attract = AttractMode::new(0, 7);
+        idle(&mut attract, &mut game, 60 * TICKS_PER_SECOND);
+        assert!(!attract.is_active());
+    }
+
+    #[test]
+    fn first_key_hands_control_back() {
+        let mut game = GameState::new().with_seed(7);
+        let mut attract = AttractMode::new(1, 7);
+        idle(&mut attract, &mut game, 2 * TICKS_PER_SECOND);
+        assert!(attract.is_active());
+
+        attract.on_key(&mut game);
+        assert!(!attract.is_active());
+        assert!(!game.demo);
+        // The wait starts over from the key
+        idle(&mut attract, &mut game, TICKS_PER_SECOND);
+        assert!(!attract.is_active());
+    }
+
+    #[test]
+    fn demo_is_tagged_and_its_points_not_saved() {
+        let step = Duration::from_secs(1) / TICKS_PER_SECOND as u32;
+        let mut runner = Runner::new(GameState::new().with_seed(7)).with_attract(1);
+        for _ in 0..TICKS_PER_SECOND {
+            runner.tick(step);
+        }
+        assert_eq!(runner.tag(), None);
+
+        for _ in 0..2 * TICKS_PER_SECOND {
+            runner.tick(step);
+        }
+        assert_eq!(runner.tag(), Some("demo"));
+        let game = runner.game();
+        assert!(game.demo_score > 0);
+        assert_eq!(game.player_score(), game.score - game.demo_score);
+        let run = RunRecord::from_game(game, EndReason::Quit, SystemTime::now());
+        assert_eq!(run.score, game.player_score());
+        assert!(run.score < game.score);
+
+        runner.feed_action(GameAction::None);
+        assert_eq!(runner.tag(), None);
+        let demo_score = runner.game().demo_score;
+        runner.tick(step);
+        assert_eq!(runner.game().demo_score, demo_score);
+    }
+}
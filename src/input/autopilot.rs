@@ -0,0 +1,123 @@
+use super::{GameAction, Policy};
+use crate::game::rng::Rng;
+use crate::game::state::{GameState, Obstacle, Player, PlayerState};
+
+/// Reaction delay ceiling at skill 0, in ticks
+const MAX_REACTION_TICKS: f32 = 8.0;
+
+/// Chance of fumbling a decision at skill 0
+const MAX_MISTAKE_CHANCE: f32 = 0.35;
+
+/// Ticks before impact a perfect player jumps or ducks
+const JUMP_LEAD_TICKS: f32 = 3.0;
+const DUCK_LEAD_TICKS: f32 = 4.0;
+
+/// Keeps the autopilot's rolls apart from the spawner's
+const AUTOPILOT_SALT: u64 = 0xa070_b107;
+
+/// Bot that plays from what's on screen
+/// At skill 1.0 it reacts instantly and never slips; lower skill adds a
+/// random reaction delay and a chance of freezing or doing the wrong thing
+#[derive(Debug)]
+pub struct Autopilot {
+    skill: f32,
+    rng: Rng,
+    pending: Option<(u64, GameAction)>, // Decided, waiting out the reaction delay
+    busy_until: u64,                    // Frame the current obstacle is dealt with
+}
+
+impl Autopilot {
+    /// `skill` is clamped to 0.0..=1.0
+    pub fn new(skill: f32, seed: u64) -> Self {
+        Self {
+            skill: skill.clamp(0.0, 1.0),
+            rng: Rng::new(seed ^ AUTOPILOT_SALT),
+            pending: None,
+            busy_until: 0,
+        }
+    }
+
+    /// What a perfect player would do right now, and for how many ticks
+    /// that settles things
+    fn plan(&self, game: &GameState) -> Option<(GameAction, u64)> {
+        let player = &game.player;
+        let ducking = player.state == PlayerState::Ducking;
+
+        // Mid-jump there's nothing to decide until we land
+        if player.y > 0.0 || player.velocity_y != 0.0 {
+            return None;
+        }
+
+        let scroll = game.scroll_speed();
+        let player_right = player.x + 1.0 + player.hitbox_width();
+        let Some(next) = next_obstacle(game) else {
+            return ducking.then_some((GameAction::ToggleDuck, 0));
+        };
+        let distance = next.x + 1.0 - player_right;
+        let ticks_to_impact = distance / scroll;
+        let ticks_to_clear =
+            (distance + player.hitbox_width() + next.obstacle_type.hitbox_width() as f32) / scroll;
+
+        // Anything floating above our head can be ignored; anything above a
+        // ducked head gets ducked under; the rest gets jumped
+        let bottom = next.obstacle_type.fly_height() as f32;
+        if bottom >= Player::hitbox_height_when(PlayerState::Running) {
+            ducking.then_some((GameAction::ToggleDuck, 0))
+        } else if bottom >= Player::hitbox_height_when(PlayerState::Ducking) {
+            (!ducking && ticks_to_impact < DUCK_LEAD_TICKS)
+                .then_some((GameAction::ToggleDuck, ticks_to_clear.ceil() as u64))
+        } else if ducking {
+            Some((GameAction::ToggleDuck, 0))
+        } else {
+            (ticks_to_impact < JUMP_LEAD_TICKS)
+                .then_some((GameAction::Jump, ticks_to_clear.ceil() as u64))
+        }
+    }
+}
+
+impl Policy for Autopilot {
+    fn act(&mut self, game: &GameState) -> GameAction {
+        if let Some((due, action)) = self.pending {
+            if game.frame_count < due {
+                return GameAction::None;
+            }
+            self.pending = None;
+            return action;
+        }
+        if game.frame_count < self.busy_until {
+            return GameAction::None;
+        }
+
+        let Some((mut action, ticks_to_clear)) = self.plan(game) else {
+            return GameAction::None;
+        };
+        self.busy_until = game.frame_count + ticks_to_clear;
+
+        let clumsiness = 1.0 - self.skill;
+        if self.rng.chance(clumsiness * MAX_MISTAKE_CHANCE) {
+            // Half the time freeze, half the time press the wrong key
+            action = match (self.rng.range(0, 2), action) {
+                (0, _) => GameAction::None,
+                (_, GameAction::Jump) => GameAction::ToggleDuck,
+                (_, _) => GameAction::Jump,
+            };
+        }
+
+        let max_delay = (clumsiness * MAX_REACTION_TICKS).round() as u64;
+        let delay = self.rng.range(0, max_delay + 1);
+        if delay == 0 {
+            return action;
+        }
+        self.pending = Some((game.frame_count + delay, action));
+        GameAction::None
+    }
+}
+
+/// Nearest obstacle whose hitbox hasn't gone past the player yet
+fn next_obstacle(game: &GameState) -> Option<&Obstacle> {
+    let player_left = game.player.x + 1.0;
+    game.obstacles
+        .iter()
+        .filter(|o| o.x + 1.0 + o.obstacle_type.hitbox_width() as f32 > player_left)
+        .min_by(|a, b| a.x.total_cmp(&b.x))
+}
@@ -1,29 +1,56 @@
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::io;
 use std::time::Duration;
+
 /// Actions that can result from input
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GameAction {
+    /// Jump, if on the ground
     Jump,
+    /// Start or stop ducking
     ToggleDuck,
+    /// Leave the game
     Quit,
+    /// A key with nothing bound to it
     None,
 }
 
 /// Poll for keyboard input with a timeout
 /// Returns None if no event, Some(action) otherwise
-pub fn poll_input(timeout: Duration) -> std::io::Result<Option<GameAction>> {
-    if event::poll(timeout)? {
-        if let Event::Key(key) = event::read()? {
-            // Only respond to key press, not release (release events unreliable cross-platform)
-            if key.kind == KeyEventKind::Press {
-                return Ok(Some(handle_key(key)));
-            }
-        }
+pub fn poll_input(timeout: Duration) -> io::Result<Option<GameAction>> {
+    if event::poll(timeout)?
+        && let Event::Key(key) = event::read()?
+        // Only respond to key press, not release (release events unreliable cross-platform)
+        && key.kind == KeyEventKind::Press
+    {
+        return Ok(Some(handle_key(key)));
     }
     Ok(None)
 }
 
-fn handle_key(key: KeyEvent) -> GameAction {
+/// Every key press already waiting, without blocking
+/// A frame handles all the keys typed since the last one, however low
+/// the frame rate.
+pub fn pending_input() -> io::Result<Vec<GameAction>> {
+    let mut actions = Vec::new();
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            actions.push(handle_key(key));
+        }
+    }
+    Ok(actions)
+}
+
+/// Wait until input arrives or `timeout` passes, without reading it
+/// Returns true if there's input to read.
+pub fn wait_for_input(timeout: Duration) -> io::Result<bool> {
+    event::poll(timeout)
+}
+
+/// The default key map: space/up/w/k jump, down/s/j duck, q/esc/ctrl-c quit
+pub fn handle_key(key: KeyEvent) -> GameAction {
     match key.code {
         // Jump keys
         KeyCode::Char(' ') => GameAction::Jump,
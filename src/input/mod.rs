@@ -1,3 +1,18 @@
+/// Letting the autopilot play when the player looks away
+pub mod attract;
+/// A bot that plays the game
+pub mod autopilot;
+/// Reading keys and mapping them to actions
 pub mod events;
 
-pub use events::{poll_input, GameAction};
+pub use attract::AttractMode;
+pub use autopilot::Autopilot;
+pub use events::{handle_key, pending_input, poll_input, wait_for_input, GameAction};
+
+use crate::game::GameState;
+
+/// Something that plays the game in place of the keyboard
+pub trait Policy {
+    /// Called once per tick, before the tick runs
+    fn act(&mut self, game: &GameState) -> GameAction;
+}
@@ -0,0 +1,38 @@
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+
+/// The terminal the user launched Claude Code from
+fn open_tty() -> io::Result<File> {
+    OpenOptions::new().read(true).write(true).open("/dev/tty")
+}
+
+/// Start the game with the controlling terminal as its stdio and return
+/// without waiting. The game learns that compaction finished through its
+/// control socket or the transcript, not from us.
+/// The child runs in a session of its own: as a background process group
+/// of Claude Code's terminal it would be stopped with SIGTTOU as soon as
+/// it switched to raw mode. Claude Code still reads the same terminal, so
+/// keys can go to either process.
+pub fn spawn_on_tty(exe: &Path, args: &[OsString]) -> io::Result<()> {
+    let tty = open_tty()?;
+    let mut command = Command::new(exe);
+    command
+        .args(args)
+        .stdin(tty.try_clone()?)
+        .stdout(tty.try_clone()?)
+        .stderr(tty);
+    // SAFETY: setsid is async-signal-safe and touches no memory
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    command.spawn().map(|_| ())
+}
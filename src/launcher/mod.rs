@@ -0,0 +1,177 @@
+/// Running in the background on the controlling terminal
+pub mod detach;
+/// tmux popups and panes, screen windows
+pub mod multiplexer;
+
+use clap::ValueEnum;
+use std::ffi::OsString;
+use std::io::{self, IsTerminal};
+
+/// Where the game process runs
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum Launcher {
+    /// tmux popup inside tmux, screen window inside screen, otherwise here
+    #[default]
+    Auto,
+    /// tmux display-popup over the current pane
+    Tmux,
+    /// New tmux pane below the current one
+    TmuxSplit,
+    /// New window in the current screen session
+    Screen,
+    /// Background process drawing on the controlling terminal
+    /// It shares the terminal with Claude Code, which still reads keys from
+    /// it, so it's never picked automatically.
+    Detach,
+    /// Run in this process and terminal
+    Foreground,
+}
+
+impl Launcher {
+    /// Pick a concrete launcher for `Auto`
+    /// A terminal on stdout means someone ran us by hand, so stay put.
+    /// Otherwise we're probably a hook without a TTY: use a multiplexer if
+    /// we're in one. None if there's nowhere to draw, since running here
+    /// would draw into the pipe Claude Code reads.
+    pub fn resolve(self) -> Option<Launcher> {
+        if self != Launcher::Auto {
+            return Some(self);
+        }
+        if io::stdout().is_terminal() {
+            Some(Launcher::Foreground)
+        } else {
+            Self::multiplexer()
+        }
+    }
+
+    /// The tmux or screen session we're running in, if any
+    pub fn multiplexer() -> Option<Launcher> {
+        if std::env::var_os("TMUX").is_some() {
+            Some(Launcher::Tmux)
+        } else if std::env::var_os("STY").is_some() {
+            Some(Launcher::Screen)
+        } else {
+            None
+        }
+    }
+}
+
+/// Arguments for the re-exec'd game process
+/// `argv` is our own command line, program name first. The child gets it
+/// minus any launcher/payload flags, then a forced foreground launcher and
+/// the hook payload (stdin doesn't follow us).
+/// `watch_pid` is the Claude Code process we found, since the child's own
+/// ancestry leads back to tmux rather than Claude.
+pub fn child_args(
+    argv: impl IntoIterator<Item = OsString>,
+    payload: Option<&str>,
+    watch_pid: Option<u32>,
+) -> Vec<OsString> {
+    let mut args = Vec::new();
+    let mut skip_value = false;
+    for arg in argv.into_iter().skip(1) {
+        if skip_value {
+            skip_value = false;
+            continue;
+        }
+        let text = arg.to_string_lossy();
+        if text == "--launcher" || text == "--hook-payload" {
+            skip_value = true;
+            continue;
+        }
+        if text.starts_with("--launcher=") || text.starts_with("--hook-payload=") {
+            continue;
+        }
+        args.push(arg);
+    }
+
+    args.push("--launcher".into());
+    args.push("foreground".into());
+    if let Some(payload) = payload {
+        args.push("--hook-payload".into());
+        args.push(payload.into());
+    }
+    if let Some(pid) = watch_pid {
+        args.push("--watch-pid".into());
+        args.push(pid.to_string().into());
+    }
+    args
+}
+
+/// Start the game somewhere else and return without waiting for it
+pub fn relaunch(
+    launcher: Launcher,
+    payload: Option<&str>,
+    watch_pid: Option<u32>,
+    height: u16,
+) -> io::Result<()> {
+    let exe = std::env::current_exe()?;
+    let args = child_args(std::env::args_os(), payload, watch_pid);
+    match launcher {
+        Launcher::Tmux => multiplexer::tmux_popup(&exe, &args, height),
+        Launcher::TmuxSplit => multiplexer::tmux_split(&exe, &args, height),
+        Launcher::Screen => multiplexer::screen_window(&exe, &args),
+        Launcher::Detach => detach::spawn_on_tty(&exe, &args),
+        Launcher::Auto | Launcher::Foreground => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child(argv: &[&str]) -> Vec<String> {
+        child_args(argv.iter().map(OsString::from), Some("{}"), Some(42))
+            .into_iter()
+            .map(|a| a.into_string().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn child_runs_in_the_foreground_with_the_payload() {
+        assert_eq!(
+            child(&[
+                "clawd-runner",
+                "--launcher",
+                "tmux",
+                "--force",
+                "--hook-payload",
+                "old"
+            ]),
+            [
+                "--force",
+                "--launcher",
+                "foreground",
+                "--hook-payload",
+                "{}",
+                "--watch-pid",
+                "42"
+            ]
+        );
+        assert_eq!(
+            child(&[
+                "clawd-runner",
+                "--launcher=screen",
+                "--hook-payload={}",
+                "--force"
+            ])[..3],
+            ["--force", "--launcher", "foreground"]
+        );
+    }
+
+    #[test]
+    fn other_arguments_pass_through_in_order() {
+        let args = child(&[
+            "clawd-runner",
+            "--fps",
+            "60",
+            "--launcher",
+            "auto",
+            "--demo",
+        ]);
+        assert_eq!(
+            args[..5],
+            ["--fps", "60", "--demo", "--launcher", "foreground"]
+        );
+    }
+}
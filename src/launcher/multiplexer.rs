@@ -0,0 +1,67 @@
+use std::ffi::OsString;
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::hook::settings::shell_quote;
+
+/// Open a tmux popup sized to the game
+pub fn tmux_popup(exe: &Path, args: &[OsString], height: u16) -> io::Result<()> {
+    // +2 rows for the popup border
+    let height = (height + 2).to_string();
+    spawn_detached(
+        Command::new("tmux")
+            .args([
+                "display-popup",
+                "-E",
+                "-w",
+                "100%",
+                "-h",
+                &height,
+                "-T",
+                " clawd-runner ",
+            ])
+            .arg(shell_command(exe, args)),
+    )
+}
+
+/// Split the current tmux pane and run the game in the new one
+pub fn tmux_split(exe: &Path, args: &[OsString], height: u16) -> io::Result<()> {
+    let height = height.to_string();
+    spawn_detached(
+        Command::new("tmux")
+            .args(["split-window", "-v", "-l", &height])
+            .arg(shell_command(exe, args)),
+    )
+}
+
+/// Open a new window in the current screen session
+pub fn screen_window(exe: &Path, args: &[OsString]) -> io::Result<()> {
+    spawn_detached(
+        Command::new("screen")
+            .args(["-X", "screen", "-t", "clawd-runner", "sh", "-c"])
+            .arg(shell_command(exe, args)),
+    )
+}
+
+/// One shell-quoted command line; tmux runs its command through sh
+fn shell_command(exe: &Path, args: &[OsString]) -> String {
+    std::iter::once(exe.as_os_str())
+        .chain(args.iter().map(OsString::as_os_str))
+        .map(|arg| shell_quote(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Run the multiplexer client in its own process group with no stdio, and
+/// don't wait for it: the hook has to return so compaction can proceed
+fn spawn_detached(command: &mut Command) -> io::Result<()> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()
+        .map(|_| ())
+}
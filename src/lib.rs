@@ -0,0 +1,63 @@
+//! Clawd Runner: a terminal side-scroller to play while Claude Code compacts.
+//!
+//! The engine is plain data plus a few methods: build a [`GameState`], call
+//! [`GameState::jump`] and [`GameState::toggle_duck`] as input arrives (see
+//! [`handle_key`] for the default key map), call [`GameState::tick`] and
+//! [`GameState::maybe_spawn_obstacle`] at [`TICKS_PER_SECOND`], and draw it with
+//! the [`GameScene`] widget. The engine and widgets never touch the terminal,
+//! so they can sit in any ratatui layout; only the helpers the
+//! `clawd-runner` binary uses to run standalone ([`render::InlineTerminal`],
+//! [`pending_input`], [`wait_for_input`] and [`replay::play`]) do.
+//!
+//! ```
+//! use clawd_runner::{GameScene, GameState};
+//! use ratatui::{backend::TestBackend, Terminal};
+//!
+//! let mut game = GameState::new().with_terminal_width(60).with_seed(7);
+//! let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+//! for frame in 0..90 {
+//!     if frame % 45 == 0 {
+//!         game.jump();
+//!     }
+//!     game.tick();
+//!     game.maybe_spawn_obstacle();
+//! }
+//! terminal
+//!     .draw(|f| f.render_widget(GameScene::new(&game), f.area()))
+//!     .unwrap();
+//! assert_eq!(game.frame_count, 90);
+//! ```
+
+#![warn(missing_docs)]
+
+/// Achievements: the catalog, tracking them during a run and saved unlocks
+pub mod achievements;
+/// Control socket that lets hooks and other runners reach a running game
+pub mod control;
+/// Game state, physics, obstacle spawning and timing
+pub mod game;
+/// Claude Code hook payloads and settings.json editing
+pub mod hook;
+/// Keyboard mapping and computer players
+pub mod input;
+/// Starting the game somewhere a hook can't: tmux, screen or the tty
+pub mod launcher;
+/// ratatui widgets for the scene, the end banner and the inline viewport
+pub mod render;
+/// Recording runs and playing them back
+pub mod replay;
+/// A controller and widget for hosting the game in another ratatui app
+pub mod runner;
+/// Headless batch runs for balance testing
+pub mod sim;
+/// Where persistent files live
+pub mod storage;
+/// Watching transcripts, sessions and the Claude Code process
+pub mod watcher;
+
+pub use game::physics::TICKS_PER_SECOND;
+pub use game::{EndReason, GameState};
+pub use input::{GameAction, handle_key, pending_input, poll_input, wait_for_input};
+pub use render::{EndScreen, GameScene};
+pub use runner::{Runner, RunnerWidget};
+pub use watcher::{TranscriptWatcher, WatchEvent};
@@ -1,24 +1,23 @@
-mod game;
-mod input;
-mod render;
-mod watcher;
-
-use clap::Parser;
-use crossterm::{
-    cursor,
-    execute,
-    terminal::{self},
-};
-use ratatui::{backend::CrosstermBackend, Terminal};
-use serde::Deserialize;
-use std::io::{self, Read};
+use clap::{Parser, Subcommand};
+use std::io;
 use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
-use game::GameState;
-use input::{poll_input, GameAction};
-use render::GameScene;
-use watcher::TranscriptWatcher;
+use clawd_runner::achievements::{Tracker, Unlocks};
+use clawd_runner::control::{self, ControlMessage};
+use clawd_runner::game::physics::TICKS_PER_SECOND;
+use clawd_runner::game::rng::Rng;
+use clawd_runner::game::{EndReason, GameState};
+use clawd_runner::hook::{self, CompactTrigger, HookInput, HookInputError, Scope, SettingsFile};
+use clawd_runner::input::{pending_input, wait_for_input};
+use clawd_runner::launcher::{self, Launcher};
+use clawd_runner::render::{InlineTerminal, GAME_HEIGHT};
+use clawd_runner::replay::{self, ReplaySpeed};
+use clawd_runner::runner::{Runner, RunnerWidget};
+use clawd_runner::sim::{self, PolicyKind, SimConfig};
+use clawd_runner::storage::{RunRecord, ScoreBook, Stats};
+use clawd_runner::watcher::sessions::{project_dir, session_id_for, session_label};
+use clawd_runner::watcher::{self, Backend, EndWhen, Waiter};
 
 /// Claude Compact Runner - A game to play while Claude Code compacts
 #[derive(Parser, Debug)]
@@ -26,18 +25,38 @@ use watcher::TranscriptWatcher;
 #[command(about = "A terminal side-scroller game for Claude Code compaction")]
 #[command(version)]
 struct Args {
-    /// Path to transcript file to watch
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Path to transcript file to watch (repeat to watch several sessions)
     #[arg(short, long)]
-    transcript: Option<PathBuf>,
+    transcript: Vec<PathBuf>,
+
+    /// Which sessions must finish compacting before the game ends
+    #[arg(long, value_enum, default_value_t = EndWhen::Launcher)]
+    end_when: EndWhen,
+
+    /// Start a separate game even if one is already running for another session
+    #[arg(long)]
+    no_join: bool,
 
     /// Max duration before auto-exit (seconds)
-    #[arg(short, long, default_value = "300")]
-    duration: u64,
+    /// [default: 300 for auto compaction, 120 for /compact]
+    #[arg(short, long)]
+    duration: Option<u64>,
 
     /// Initial speed multiplier
     #[arg(short, long, default_value = "1.0")]
     speed: f32,
 
+    /// Seed for obstacle generation (default: random, shown on the end screen)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Frames drawn per second; game speed doesn't depend on it
+    #[arg(long, global = true, default_value = "30", value_parser = clap::value_parser!(u16).range(1..=240))]
+    fps: u16,
+
     /// Disable colors
     #[arg(long)]
     no_color: bool,
@@ -45,158 +64,547 @@ struct Args {
     /// Run in demo mode (no file watching, manual exit only)
     #[arg(long)]
     demo: bool,
+
+    /// How to wait for transcript changes
+    #[arg(long, value_enum, default_value_t = Backend::Auto)]
+    watch_backend: Backend,
+
+    /// How long to wait for hook input on stdin (milliseconds)
+    #[arg(long, default_value = "1000")]
+    stdin_timeout: u64,
+
+    /// Launch even when invoked by a hook event other than PreCompact
+    #[arg(long)]
+    force: bool,
+
+    /// Where to run the game (hooks have no usable terminal of their own)
+    #[arg(long, value_enum, default_value_t = Launcher::Auto)]
+    launcher: Launcher,
+
+    /// End the game when this process exits (default: the Claude Code
+    /// process that ran the hook)
+    #[arg(long)]
+    watch_pid: Option<u32>,
+
+    /// Don't watch the Claude Code process that ran the hook
+    #[arg(long, conflicts_with = "watch_pid")]
+    no_watch_pid: bool,
+
+    /// How long the end screen stays up after compaction finishes (seconds)
+    #[arg(long, global = true, default_value = "3")]
+    grace: u16,
+
+    /// Keep the game running after compaction finishes until you press a key
+    #[arg(long)]
+    keep_playing: bool,
+
+    /// Let the autopilot play at this skill, from 0.0 (clumsy) to 1.0 (perfect)
+    #[arg(long, value_name = "SKILL")]
+    autopilot: Option<f32>,
+
+    /// Let the autopilot take over after this many seconds without a key
+    /// press (0 to never); scores from those stretches don't count
+    #[arg(long, value_name = "SECS", default_value = "10")]
+    idle_after: u64,
+
+    /// Save the run's inputs to this file for `clawd-runner replay`
+    #[arg(long)]
+    record: Option<PathBuf>,
+
+    /// Hook payload forwarded by a launcher in place of stdin
+    #[arg(long, hide = true)]
+    hook_payload: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Add our PreCompact and SessionStart hooks to a Claude Code settings file
+    InstallHook {
+        #[command(flatten)]
+        target: SettingsTarget,
+
+        /// Only launch for this trigger (default: both)
+        #[arg(long, value_enum)]
+        trigger: Option<CompactTrigger>,
+
+        /// Hook command to run instead of this binary
+        #[arg(long)]
+        command: Option<String>,
+    },
+
+    /// Remove our hooks from a Claude Code settings file, leaving others alone
+    UninstallHook {
+        #[command(flatten)]
+        target: SettingsTarget,
+    },
+
+    /// Tell the game running for a session that compaction finished
+    Signal {
+        #[arg(value_enum)]
+        message: ControlMessage,
+
+        /// Session to signal (default: session_id from hook input on stdin)
+        #[arg(long)]
+        session: Option<String>,
+    },
+
+    /// Play back a run saved with --record
+    Replay {
+        /// Recording to play
+        file: PathBuf,
+
+        /// Playback speed
+        #[arg(long, value_enum, default_value_t = ReplaySpeed::Normal)]
+        speed: ReplaySpeed,
+    },
+
+    /// Play many runs headless and print balance statistics as JSON
+    Simulate {
+        /// Number of runs
+        #[arg(long, default_value = "100")]
+        runs: u32,
+
+        /// Ticks per run [default: 5 minutes of game time]
+        #[arg(long, default_value_t = 300 * TICKS_PER_SECOND)]
+        frames: u64,
+
+        /// Who plays
+        #[arg(long, value_enum, default_value_t = PolicyKind::Autopilot)]
+        policy: PolicyKind,
+
+        /// Autopilot skill from 0.0 (slow and clumsy) to 1.0 (perfect)
+        #[arg(long, default_value = "1.0")]
+        skill: f32,
+
+        /// Seed of the first run; each following run adds one
+        #[arg(long, default_value = "1")]
+        seed: u64,
+
+        /// Terminal width to simulate
+        #[arg(long, default_value = "80")]
+        width: u16,
+
+        /// Initial speed multiplier
+        #[arg(long, default_value = "1.0")]
+        speed: f32,
+    },
+
+    /// Print lifetime stats from saved runs
+    Stats {
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// List achievements, locked and unlocked
+    Achievements,
 }
 
-/// Hook input JSON from Claude Code
-#[derive(Deserialize, Debug)]
-struct HookInput {
-    #[serde(default)]
-    transcript_path: Option<String>,
-    #[serde(default)]
-    session_id: Option<String>,
-    #[serde(default)]
-    hook_event_name: Option<String>,
+/// Which settings file a hook subcommand edits
+#[derive(clap::Args, Debug)]
+struct SettingsTarget {
+    /// Settings file to edit
+    #[arg(long, value_enum, default_value_t = Scope::User)]
+    scope: Scope,
+
+    /// Edit this file instead of the one picked by --scope
+    #[arg(long)]
+    settings: Option<PathBuf>,
+
+    /// Print the change as a diff without writing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl SettingsTarget {
+    fn load(&self) -> io::Result<SettingsFile> {
+        let path = match self.settings {
+            Some(ref path) => path.clone(),
+            None => self.scope.path()?,
+        };
+        SettingsFile::load(path)
+    }
+
+    /// Print the diff for a dry run, otherwise write the file
+    fn apply(&self, settings: &SettingsFile) -> io::Result<()> {
+        if self.dry_run {
+            print!("{}", settings.diff()?);
+            return Ok(());
+        }
+        if let Some(backup) = settings.save()? {
+            println!("Backed up previous settings to {}", backup.display());
+        }
+        Ok(())
+    }
 }
 
 fn main() -> io::Result<()> {
     let args = Args::parse();
 
-    // Determine transcript path
-    let transcript_path = if args.demo {
-        None
-    } else if let Some(path) = args.transcript {
-        Some(path)
+    match args.command {
+        Some(Command::InstallHook { ref target, trigger, ref command }) => {
+            return install_hook(target, trigger, command.as_deref());
+        }
+        Some(Command::UninstallHook { ref target }) => return uninstall_hook(target),
+        Some(Command::Signal { message, ref session }) => {
+            return signal(message, session.clone(), args.stdin_timeout);
+        }
+        Some(Command::Replay { ref file, speed }) => {
+            return replay::play(file, speed, args.grace, args.fps);
+        }
+        Some(Command::Simulate { runs, frames, policy, skill, seed, width, speed }) => {
+            let config = SimConfig { runs, frames, policy, skill, seed, terminal_width: width, speed };
+            println!("{}", serde_json::to_string_pretty(&sim::run(&config))?);
+            return Ok(());
+        }
+        Some(Command::Stats { json }) => {
+            let stats = Stats::new(&ScoreBook::read(&ScoreBook::path()?)?);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+            } else {
+                print!("{stats}");
+            }
+            return Ok(());
+        }
+        Some(Command::Achievements) => {
+            print!("{}", Unlocks::read(&Unlocks::path()?)?);
+            return Ok(());
+        }
+        None => {}
+    }
+
+    launch(&args)
+}
+
+/// Work out what we're waiting on, then play here or hand off to a
+/// running game or another pane
+fn launch(args: &Args) -> io::Result<()> {
+    let hook_input = read_hook_input(args);
+
+    if let Some(ref input) = hook_input
+        && !input.is_compaction()
+        && !args.force
+    {
+        // Not an error: the hook is just wired to the wrong event
+        eprintln!(
+            "clawd-runner: not launching for {} hook (only {} starts the game, pass --force to override)",
+            input.hook_event_name.as_deref().unwrap_or("unknown"),
+            hook::PRE_COMPACT,
+        );
+        return Ok(());
+    }
+
+    // If Claude Code dies mid-compaction there's nothing left to wait for.
+    // Only the hook process itself can find Claude by walking up the tree;
+    // a relaunched game gets the pid passed along.
+    let launched_by_hook = hook_input.is_some() && args.hook_payload.is_none();
+    let detected_pid = (launched_by_hook && !args.no_watch_pid && args.watch_pid.is_none())
+        .then(watcher::process::find_claude)
+        .flatten();
+    let watch_pid = args.watch_pid.or(detected_pid);
+
+    // Another session's game is already up: have it watch this session too
+    // rather than stacking a second game on top
+    if launched_by_hook
+        && !args.no_join
+        && let Some(ref input) = hook_input
+        && input.session_id.is_some()
+        && control::join(&serde_json::to_string(input)?).unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    // Hand off to a tmux/screen pane and let the hook return right away
+    let Some(launcher) = args.launcher.resolve() else {
+        // Running here would draw into Claude Code's pipe; never hold up
+        // compaction over it
+        eprintln!("clawd-runner: no terminal to play in (run Claude Code inside tmux or screen, or pass --launcher)");
+        return Ok(());
+    };
+    if launcher != Launcher::Foreground {
+        let payload = hook_input.as_ref().map(serde_json::to_string).transpose()?;
+        return launcher::relaunch(launcher, payload.as_deref(), detected_pid, GAME_HEIGHT);
+    }
+
+    let targets = if args.demo {
+        Vec::new()
     } else {
-        // Try to read from stdin (hook input)
-        read_transcript_from_stdin()
+        session_targets(hook_input.as_ref(), &args.transcript)
     };
+    play(args, hook_input.as_ref(), targets, watch_pid)
+}
+
+/// Run the game in this terminal until it ends, then save the results
+fn play(
+    args: &Args,
+    hook_input: Option<&HookInput>,
+    targets: Vec<(String, String, Option<PathBuf>)>,
+    watch_pid: Option<u32>,
+) -> io::Result<()> {
+    // Stats are kept per project and count time spent waiting on compaction
+    let project = targets
+        .first()
+        .and_then(|(_, _, transcript)| transcript.as_deref())
+        .and_then(project_dir);
+    let session_id = hook_input.and_then(|i| i.session_id.clone());
+    let waiting = !targets.is_empty();
 
-    // Set up terminal for inline rendering
-    terminal::enable_raw_mode()?;
-    let mut stdout = io::stdout();
-
-    // Print newlines to make room for the game area, then move cursor back up
-    const GAME_HEIGHT: u16 = 8;
-    for _ in 0..GAME_HEIGHT {
-        println!();
-    }
-    execute!(stdout, cursor::MoveUp(GAME_HEIGHT), cursor::Hide)?;
-
-    // Save the starting position
-    let (start_col, start_row) = cursor::position()?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::with_options(
-        backend,
-        ratatui::TerminalOptions {
-            viewport: ratatui::Viewport::Fixed(ratatui::layout::Rect::new(
-                start_col,
-                start_row,
-                terminal::size()?.0,
-                GAME_HEIGHT,
-            )),
-        },
-    )?;
+    let trigger = hook_input
+        .and_then(|i| i.trigger)
+        .unwrap_or(CompactTrigger::Auto);
+    let duration = args.duration.unwrap_or_else(|| trigger.default_duration());
+
+    let mut terminal = InlineTerminal::enter()?;
 
     // Get terminal size
     let size = terminal.size()?;
 
+    // Best score before this run, for the "NEW HIGH" badge, and how the
+    // session's earlier compactions went
+    let scores = ScoreBook::load();
+    let best = scores.best();
+    let session = session_id.as_deref().and_then(|id| scores.session(id));
+
     // Initialize game state
-    let mut game = GameState::new().with_terminal_width(size.width);
+    let seed = args.seed.unwrap_or_else(Rng::random_seed);
+    let mut game = GameState::new()
+        .with_terminal_width(size.width)
+        .with_seed(seed);
     game.speed = args.speed;
-
-    // Set up file watcher
-    let watcher: Option<TranscriptWatcher> = transcript_path
-        .as_ref()
-        .and_then(|p| TranscriptWatcher::new(p.clone()).ok());
+    let mut runner = Runner::new(game)
+        .with_grace(args.grace)
+        .with_keep_playing(args.keep_playing)
+        .with_attract(args.idle_after);
+    if let Some(skill) = args.autopilot {
+        runner = runner.with_autopilot(skill);
+    }
+    if args.record.is_some() {
+        runner = runner.with_recording();
+    }
+    // The autopilot can't earn achievements; it doesn't get to try
+    if args.autopilot.is_none() {
+        let compactions = scores.compactions() + u32::from(waiting);
+        runner = runner.with_achievements(Tracker::new(&Unlocks::load(), compactions));
+    }
 
     let start_time = Instant::now();
-    let max_duration = Duration::from_secs(args.duration);
-    let frame_duration = Duration::from_millis(33); // ~30fps
+
+    // Watch each session's transcript on its own thread and listen for
+    // `clawd-runner signal done` from its post-compaction hook
+    let mut waiter = Waiter::new(start_time, args.watch_backend, args.end_when);
+    for (id, label, transcript) in targets {
+        waiter.add_session(id, label, transcript);
+    }
+    if let Some(pid) = watch_pid.filter(|_| !args.demo) {
+        waiter.watch_pid(pid);
+    }
+
+    let max_duration = Duration::from_secs(duration);
+    // Game time advances in fixed ticks; drawing happens at --fps
+    let frame_duration = Duration::from_secs(1) / u32::from(args.fps);
+    let mut last_frame = Instant::now();
+    let mut ended_at = None;
 
     // Main game loop
     loop {
         let frame_start = Instant::now();
 
         // Check exit conditions
-        if game.should_quit {
+        if runner.is_finished() {
             break;
         }
 
         // Check timeout
         if start_time.elapsed() >= max_duration {
-            break;
+            runner.finish(EndReason::Timeout);
         }
 
-        // Check file watcher
-        if let Some(ref w) = watcher {
-            if w.file_changed().unwrap_or(false) {
-                break;
-            }
+        // Check whether the watcher or a hook reported the compaction
+        // finished, or Claude Code went away
+        if let Some(reason) = waiter.poll() {
+            runner.finish(reason);
         }
 
-        // Handle input (non-blocking with short timeout)
-        let input_timeout = Duration::from_millis(5);
-        if let Some(action) = poll_input(input_timeout)? {
-            match action {
-                GameAction::Jump => game.jump(),
-                GameAction::ToggleDuck => game.toggle_duck(),
-                GameAction::Quit => game.should_quit = true,
-                GameAction::None => {}
-            }
+        if ended_at.is_none() && runner.game().is_ended() {
+            ended_at = Some(Instant::now());
+        }
+
+        // Handle every key pressed since the last frame
+        for action in pending_input()? {
+            runner.feed_action(action);
         }
 
-        // Update game state
-        game.tick();
-        game.maybe_spawn_obstacle();
+        // Update game state: however many ticks are due since the last frame
+        let now = Instant::now();
+        runner.tick(now - last_frame);
+        last_frame = now;
 
-        // Check terminal size and update
+        // Check terminal size
         let new_size = terminal.size()?;
         if new_size.width < 40 || new_size.height < 6 {
             // Terminal too small, exit gracefully
             break;
         }
-        game.terminal_width = new_size.width;
+        runner.resize(new_size.width);
 
         // Render
         terminal.draw(|frame| {
-            let area = frame.area();
-            frame.render_widget(GameScene::new(&game), area);
+            let widget = RunnerWidget::new()
+                .with_progress(waiter.progress(Instant::now()))
+                .with_sessions(waiter.sessions())
+                .with_best(best)
+                .with_session(session);
+            frame.render_stateful_widget(widget, frame.area(), &mut runner);
         })?;
 
-        // Frame rate limiting
+        // Frame rate limiting: wait out the frame, but let a key start the
+        // next one early so input doesn't lag at low --fps
         let elapsed = frame_start.elapsed();
         if elapsed < frame_duration {
-            std::thread::sleep(frame_duration - elapsed);
+            wait_for_input(frame_duration - elapsed)?;
+        }
+    }
+
+    terminal.leave()?;
+
+    let end = runner.game().end_reason().unwrap_or(EndReason::Quit);
+    let waited = ended_at.unwrap_or_else(Instant::now) - start_time;
+    let run = RunRecord::from_game(runner.game(), end, SystemTime::now())
+        .with_project(project)
+        .with_session(session_id)
+        .with_waited(waiting.then_some(waited));
+    save_results(args, &mut runner, run)
+}
+
+/// Save the score, any unlocks and the recording once the game is over
+fn save_results(args: &Args, runner: &mut Runner, run: RunRecord) -> io::Result<()> {
+    // A run the autopilot played start to finish isn't the player's score
+    if args.autopilot.is_none() {
+        let score = run.score;
+        match ScoreBook::path().and_then(|path| ScoreBook::record(&path, run)) {
+            Ok(true) => println!("New high score: {score}"),
+            Ok(false) => {}
+            Err(err) => eprintln!("clawd-runner: couldn't save score: {err}"),
         }
     }
 
-    // Restore terminal - move cursor below game area and show it
-    terminal::disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        cursor::MoveTo(0, start_row + GAME_HEIGHT),
-        cursor::Show
-    )?;
+    let unlocked: Vec<&str> = runner.take_unlocked().iter().map(|a| a.id).collect();
+    if !unlocked.is_empty() {
+        let saved = Unlocks::path()
+            .and_then(|path| Unlocks::record(&path, &unlocked, SystemTime::now()));
+        if let Err(err) = saved {
+            eprintln!("clawd-runner: couldn't save achievements: {err}");
+        }
+    }
+
+    if let (Some(recording), Some(path)) = (runner.take_recording(), &args.record) {
+        recording.save(path)?;
+    }
 
     Ok(())
 }
 
-fn read_transcript_from_stdin() -> Option<PathBuf> {
-    // Check if stdin has data (non-blocking check)
-    // For now, just try to read and parse JSON
-    let mut input = String::new();
-
-    // Set a short timeout for reading stdin
-    // If nothing is available, return None
-    match io::stdin().read_to_string(&mut input) {
-        Ok(_) if !input.is_empty() => {
-            if let Ok(hook_input) = serde_json::from_str::<HookInput>(&input) {
-                hook_input.transcript_path.map(PathBuf::from)
-            } else {
-                None
-            }
+/// Read the hook payload unless we were told what to do on the command line
+fn read_hook_input(args: &Args) -> Option<HookInput> {
+    let result = if let Some(ref payload) = args.hook_payload {
+        hook::input::parse(payload).map(Some)
+    } else if args.demo || !args.transcript.is_empty() {
+        Ok(None)
+    } else {
+        hook::read_from_stdin(Duration::from_millis(args.stdin_timeout))
+    };
+    match result {
+        Ok(input) => input,
+        Err(err @ HookInputError::Timeout(_)) => {
+            // Still worth playing, we just can't tell when compaction ends
+            eprintln!("clawd-runner: {err}");
+            None
+        }
+        Err(err) => {
+            eprintln!("clawd-runner: {err}");
+            std::process::exit(1);
         }
-        _ => None,
     }
 }
+
+/// Sessions to watch: the one whose hook launched us, then any extra
+/// transcripts, as (id, label, transcript)
+fn session_targets(
+    hook_input: Option<&HookInput>,
+    transcripts: &[PathBuf],
+) -> Vec<(String, String, Option<PathBuf>)> {
+    let mut targets = Vec::new();
+    if let Some(input) = hook_input
+        && let Some(transcript) = input.transcript()
+    {
+        let id = input
+            .session_id
+            .clone()
+            .unwrap_or_else(|| session_id_for(&transcript));
+        let label = session_label(input.cwd.as_deref(), &transcript);
+        targets.push((id, label, Some(transcript)));
+    }
+    for path in transcripts {
+        targets.push((session_id_for(path), session_label(None, path), Some(path.clone())));
+    }
+    targets
+}
+
+fn install_hook(target: &SettingsTarget, trigger: Option<CompactTrigger>, command: Option<&str>) -> io::Result<()> {
+    let mut settings = target.load()?;
+    if settings.has_our_hook() {
+        println!("clawd-runner hook already present in {}", settings.path().display());
+        return Ok(());
+    }
+
+    let exe = std::env::current_exe()?;
+    let command = match command {
+        Some(command) => command.to_string(),
+        None => hook::settings::default_command(&exe),
+    };
+    settings.add_our_hooks(trigger, &command, &exe)?;
+    target.apply(&settings)?;
+    if !target.dry_run {
+        println!("Installed PreCompact and SessionStart hooks in {}", settings.path().display());
+    }
+    // Hooks have no terminal: on Linux the game needs a multiplexer to open in
+    if cfg!(target_os = "linux") && Launcher::multiplexer().is_none() {
+        eprintln!(
+            "clawd-runner: warning: not inside tmux or screen; the hook only starts the game when Claude Code runs in one"
+        );
+    }
+    Ok(())
+}
+
+fn uninstall_hook(target: &SettingsTarget) -> io::Result<()> {
+    let mut settings = target.load()?;
+    if settings.remove_our_hooks() == 0 {
+        println!("No clawd-runner hook in {}", settings.path().display());
+        return Ok(());
+    }
+
+    target.apply(&settings)?;
+    if !target.dry_run {
+        println!("Removed clawd-runner hook from {}", settings.path().display());
+    }
+    Ok(())
+}
+
+fn signal(message: ControlMessage, session: Option<String>, stdin_timeout: u64) -> io::Result<()> {
+    let session = match session {
+        Some(session) => Some(session),
+        None => hook::read_from_stdin(Duration::from_millis(stdin_timeout))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?
+            .and_then(|input| input.session_id),
+    };
+    let Some(session) = session else {
+        eprintln!("clawd-runner: no session to signal (pass --session or pipe hook input)");
+        std::process::exit(1);
+    };
+
+    // Nobody listening is fine: the game may have ended already
+    if !control::signal(&session, message)? {
+        eprintln!("clawd-runner: no game running for session {session}");
+    }
+    Ok(())
+}
+
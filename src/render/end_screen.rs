@@ -0,0 +1,76 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+use crate::game::state::{EndReason, GamePhase, GameState};
+
+/// Banner drawn over the frozen scene when the run ends
+pub struct EndScreen<'a> {
+    game: &'a GameState,
+}
+
+impl<'a> EndScreen<'a> {
+    /// The banner for `game`, drawn only once it has ended
+    pub fn new(game: &'a GameState) -> Self {
+        Self { game }
+    }
+
+    fn stats_line(&self) -> String {
+        let secs = self.game.elapsed_secs();
+        // Demo points are shown but kept apart from the player's own
+        let score = match self.game.demo_score {
+            0 => self.game.score.to_string(),
+            demo => format!("{} (+{demo} demo)", self.game.player_score()),
+        };
+        format!(
+            "score {score} · {} cleared · {} hits · {}m{:02}s",
+            self.game.obstacles_cleared,
+            self.game.collisions,
+            secs / 60,
+            secs % 60
+        )
+    }
+}
+
+/// Write `text` centered on row `y`, blanking the padded span behind it
+fn draw_centered(area: Rect, buf: &mut Buffer, y: u16, text: &str, style: Style) {
+    let padded = format!(" {text} ");
+    let len = padded.chars().count() as u16;
+    let start = area.width.saturating_sub(len) / 2;
+    for (i, ch) in padded.chars().enumerate() {
+        let x = start + i as u16;
+        if x < area.width && y < area.height {
+            buf[(area.x + x, area.y + y)].set_char(ch).set_style(style);
+        }
+    }
+}
+
+impl Widget for EndScreen<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let GamePhase::Ended { reason, .. } = self.game.phase else {
+            return;
+        };
+        if area.height < 6 || area.width < 40 {
+            return;
+        }
+
+        let title = match reason {
+            EndReason::Compaction => "✓ compaction complete",
+            EndReason::ClaudeExited => "claude exited",
+            EndReason::Timeout => "time's up",
+            EndReason::Quit => "thanks for playing",
+        };
+        let hint = format!("press any key · seed {}", self.game.seed);
+
+        let title_style = Style::default()
+            .fg(Color::Indexed(119)) // Bright green, same as score pop
+            .add_modifier(Modifier::BOLD);
+        let dim = Style::default().fg(Color::Indexed(245));
+
+        draw_centered(area, buf, 0, title, title_style);
+        draw_centered(area, buf, 1, &self.stats_line(), Style::default());
+        draw_centered(area, buf, 2, &hint, dim);
+    }
+}
@@ -13,17 +13,13 @@ pub struct Ground {
 }
 
 impl Ground {
+    /// Ground scrolled `offset` columns
     pub fn new(offset: u16) -> Self {
         Self {
             offset,
             pattern_interval: 15, // Add texture every N characters
         }
     }
-
-    pub fn with_offset(mut self, offset: u16) -> Self {
-        self.offset = offset;
-        self
-    }
 }
 
 impl Widget for Ground {
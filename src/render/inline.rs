@@ -0,0 +1,97 @@
+use crossterm::{cursor, execute, terminal};
+use ratatui::{Terminal, TerminalOptions, Viewport, backend::CrosstermBackend, layout::Rect};
+use std::io::{self, Stdout};
+use std::ops::{Deref, DerefMut};
+
+/// Height of the inline game area
+pub const GAME_HEIGHT: u16 = 8;
+
+/// A GAME_HEIGHT-row area drawn in place below the cursor, leaving the
+/// rest of the terminal alone
+/// The terminal is put back when this is dropped, so an early return
+/// doesn't leave it in raw mode; `leave` does the same but reports errors.
+pub struct InlineTerminal {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    start_row: u16,
+    restored: bool,
+}
+
+impl InlineTerminal {
+    /// Switch to raw mode and reserve rows below the cursor
+    pub fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Self::reserve().inspect_err(|_| {
+            let _ = terminal::disable_raw_mode();
+            let _ = execute!(io::stdout(), cursor::Show);
+        })
+    }
+
+    fn reserve() -> io::Result<Self> {
+        let mut stdout = io::stdout();
+
+        // Print newlines to make room for the game area, then move cursor back up
+        for _ in 0..GAME_HEIGHT {
+            println!();
+        }
+        execute!(stdout, cursor::MoveUp(GAME_HEIGHT), cursor::Hide)?;
+
+        // Save the starting position
+        let (start_col, start_row) = cursor::position()?;
+
+        let backend = CrosstermBackend::new(stdout);
+        let terminal = Terminal::with_options(
+            backend,
+            TerminalOptions {
+                viewport: Viewport::Fixed(Rect::new(
+                    start_col,
+                    start_row,
+                    terminal::size()?.0,
+                    GAME_HEIGHT,
+                )),
+            },
+        )?;
+        Ok(Self {
+            terminal,
+            start_row,
+            restored: false,
+        })
+    }
+
+    /// Restore terminal - move cursor below game area and show it
+    pub fn leave(mut self) -> io::Result<()> {
+        self.restore()
+    }
+
+    fn restore(&mut self) -> io::Result<()> {
+        if self.restored {
+            return Ok(());
+        }
+        self.restored = true;
+        terminal::disable_raw_mode()?;
+        execute!(
+            self.terminal.backend_mut(),
+            cursor::MoveTo(0, self.start_row + GAME_HEIGHT),
+            cursor::Show
+        )
+    }
+}
+
+impl Drop for InlineTerminal {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+impl Deref for InlineTerminal {
+    type Target = Terminal<CrosstermBackend<Stdout>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl DerefMut for InlineTerminal {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
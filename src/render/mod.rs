@@ -1,5 +1,14 @@
+/// Character art for Clawd and the obstacles
 pub mod sprites;
+/// The scrolling ground line
 pub mod ground;
+/// The play area widget
 pub mod scene;
+/// The banner shown when a run ends
+pub mod end_screen;
+/// The inline viewport the standalone game draws in
+pub mod inline;
 
 pub use scene::GameScene;
+pub use end_screen::EndScreen;
+pub use inline::{GAME_HEIGHT, InlineTerminal};
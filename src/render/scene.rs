@@ -1,21 +1,86 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     widgets::Widget,
 };
 use crate::game::state::{GameState, PlayerState};
-use super::sprites::{ClaudeSprite, CLAUDE_COLOR, OBSTACLE_COLOR};
+use crate::storage::SessionTotals;
+use crate::watcher::{ProgressStatus, Session};
+use super::sprites::{ClaudeSprite, CLAUDE_COLOR, GROUND_COLOR, OBSTACLE_COLOR};
 use super::ground::Ground;
 
+/// Status bar badge for beating the best score
+const NEW_HIGH: &str = "NEW HIGH";
+
 /// The complete game scene widget
 pub struct GameScene<'a> {
     game: &'a GameState,
+    progress: Option<ProgressStatus>,
+    sessions: &'a [Session],
+    tag: Option<&'a str>,
+    alpha: f32,
+    best: Option<u32>,
+    session: Option<SessionTotals>,
 }
 
 impl<'a> GameScene<'a> {
+    /// The scene for `game`, without extras
     pub fn new(game: &'a GameState) -> Self {
-        Self { game }
+        Self {
+            game,
+            progress: None,
+            sessions: &[],
+            tag: None,
+            alpha: 1.0,
+            best: None,
+            session: None,
+        }
+    }
+
+    /// Show compaction progress in the status bar
+    pub fn with_progress(mut self, progress: Option<ProgressStatus>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Show one indicator per watched session (only when there's more than one)
+    pub fn with_sessions(mut self, sessions: &'a [Session]) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    /// Label the top-left corner, e.g. "replay" while playing back a run
+    pub fn with_tag(mut self, tag: Option<&'a str>) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Draw moving things `alpha` of the way from their previous tick's
+    /// position to the current one (1.0 draws the current tick as is)
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Best score from earlier runs; "NEW HIGH" shows once the player beats it
+    pub fn with_best(mut self, best: Option<u32>) -> Self {
+        self.best = best;
+        self
+    }
+
+    /// Earlier runs in this Claude Code session, shown as "session best"
+    pub fn with_session(mut self, session: Option<SessionTotals>) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// Blend a position between ticks; a frozen world has nothing to blend
+    fn lerp(&self, prev: f32, current: f32) -> f32 {
+        if self.game.is_frozen() {
+            return current;
+        }
+        prev + (current - prev) * self.alpha
     }
 
     fn render_player(&self, area: Rect, buf: &mut Buffer) {
@@ -45,7 +110,7 @@ impl<'a> GameScene<'a> {
         // Strobe on collision for high visibility
         let color = if self.game.collision_flash > 0 {
             // Alternate white/magenta every 2 frames for dramatic effect
-            if (self.game.collision_flash / 2) % 2 == 0 {
+            if (self.game.collision_flash / 2).is_multiple_of(2) {
                 Color::Indexed(231) // White
             } else {
                 Color::Indexed(199) // Hot magenta
@@ -53,11 +118,13 @@ impl<'a> GameScene<'a> {
         } else {
             CLAUDE_COLOR
         };
-        let style = Style::default().fg(color);
+        // A full style, so nothing drawn underneath (the toast) bleeds through
+        let style = Style::default().fg(color).remove_modifier(Modifier::all());
         let player_x = self.game.player.x as u16;
         let ground_y = area.height.saturating_sub(2); // Ground is 1 row, status is 1 row
         let player_bottom = ground_y;
-        let player_y = player_bottom.saturating_sub(sprite_height).saturating_sub(self.game.player.y as u16);
+        let jump_height = self.lerp(self.game.player.prev_y, self.game.player.y);
+        let player_y = player_bottom.saturating_sub(sprite_height).saturating_sub(jump_height as u16);
 
         for (row_idx, line) in sprite.iter().enumerate() {
             let y = player_y + row_idx as u16;
@@ -85,6 +152,7 @@ impl<'a> GameScene<'a> {
             // Flying obstacles are positioned above ground
             let obs_bottom = ground_y.saturating_sub(fly_height);
             let obs_top = obs_bottom.saturating_sub(height);
+            let obstacle_x = self.lerp(obstacle.prev_x, obstacle.x);
 
             for (row_idx, line) in sprite.iter().enumerate() {
                 let y = obs_top + row_idx as u16;
@@ -92,7 +160,7 @@ impl<'a> GameScene<'a> {
                     continue;
                 }
                 for (col_idx, ch) in line.chars().enumerate() {
-                    let x = obstacle.x as i32 + col_idx as i32;
+                    let x = obstacle_x as i32 + col_idx as i32;
                     if x >= 0 && (x as u16) < area.width && ch != ' ' {
                         buf[(area.x + x as u16, area.y + y)].set_char(ch).set_style(style);
                     }
@@ -101,6 +169,100 @@ impl<'a> GameScene<'a> {
         }
     }
 
+    /// Top-right row: "api ✓  web …" for each session, done ones in green
+    /// Returns the column it starts at, None if there's only one session.
+    fn render_sessions(&self, area: Rect, buf: &mut Buffer) -> Option<u16> {
+        if self.sessions.len() < 2 {
+            return None;
+        }
+
+        let pending = Style::default().fg(GROUND_COLOR);
+        let done = Style::default().fg(Color::Indexed(119)); // Bright green, same as score pop
+        let mut spans = Vec::new();
+        for (i, session) in self.sessions.iter().enumerate() {
+            if i > 0 {
+                spans.push(("  ".to_string(), pending));
+            }
+            if session.done {
+                spans.push((format!("{} ✓", session.label), done));
+            } else {
+                spans.push((format!("{} …", session.label), pending));
+            }
+        }
+
+        let len: usize = spans.iter().map(|(text, _)| text.chars().count()).sum();
+        let start = area.width.saturating_sub(len as u16);
+        let mut x = start;
+        for (text, style) in spans {
+            for ch in text.chars() {
+                if x < area.width {
+                    buf[(area.x + x, area.y)].set_char(ch).set_style(style);
+                }
+                x += 1;
+            }
+        }
+        Some(start)
+    }
+
+    /// Top-left, after the tag: this run counts toward the session's totals
+    /// Left out if it would run into the session indicators at `sessions_start`.
+    fn render_session(&self, area: Rect, buf: &mut Buffer, sessions_start: Option<u16>) {
+        let Some(session) = self.session else {
+            return;
+        };
+        let best = session.best.max(self.game.player_score());
+        let secs = session.play_secs + self.game.elapsed_secs();
+        let text = format!("session best: {best} · {}m{:02}s", secs / 60, secs % 60);
+
+        let start = self.tag.map_or(0, |tag| tag.chars().count() as u16 + 3);
+        let end = start + text.chars().count() as u16;
+        if sessions_start.is_some_and(|sessions| end + 2 > sessions) {
+            return;
+        }
+        let style = Style::default().fg(GROUND_COLOR);
+        for (i, ch) in text.chars().enumerate() {
+            let x = start + i as u16;
+            if x < area.width {
+                buf[(area.x + x, area.y)].set_char(ch).set_style(style);
+            }
+        }
+    }
+
+    /// Second row, centered: an achievement or other toast, strobing like
+    /// a milestone. Hidden once the world freezes for the end banner.
+    /// Drawn before obstacles and the player, which show through it.
+    fn render_toast(&self, area: Rect, buf: &mut Buffer) {
+        let Some(ref message) = self.game.toast else {
+            return;
+        };
+        if self.game.toast_flash == 0 || self.game.is_frozen() {
+            return;
+        }
+        let text = format!("★ {message}");
+        let style = Style::default()
+            .fg(celebration_color(self.game.toast_flash))
+            .add_modifier(Modifier::BOLD);
+        let start = area.width.saturating_sub(text.chars().count() as u16) / 2;
+        for (i, ch) in text.chars().enumerate() {
+            let x = start + i as u16;
+            if x < area.width {
+                buf[(area.x + x, area.y + 1)].set_char(ch).set_style(style);
+            }
+        }
+    }
+
+    fn render_tag(&self, area: Rect, buf: &mut Buffer) {
+        let Some(tag) = self.tag else {
+            return;
+        };
+        let style = Style::default().fg(GROUND_COLOR).add_modifier(Modifier::REVERSED);
+        for (i, ch) in format!(" {tag} ").chars().enumerate() {
+            if (i as u16) < area.width {
+                buf[(area.x + i as u16, area.y)].set_char(ch).set_style(style);
+            }
+        }
+    }
+
     fn render_status_bar(&self, area: Rect, buf: &mut Buffer) {
         if area.height == 0 {
             return;
@@ -108,31 +270,23 @@ impl<'a> GameScene<'a> {
 
         let y = area.height - 1;
 
-        // Left side: debug counters
-        let left_text = format!(
-            "cf:{} obs:{} py:{}",
-            self.game.collision_flash,
-            self.game.obstacles.len(),
-            self.game.player.y as i32
-        );
-        for (i, ch) in left_text.chars().enumerate() {
-            if (i as u16) < area.width {
-                buf[(area.x + i as u16, area.y + y)].set_char(ch);
+        // Left side: compaction progress, dots cycle like the run animation
+        let mut left_len = 0;
+        if let Some(progress) = self.progress {
+            let dots = ".".repeat((self.game.frame_count / 8 % 4) as usize);
+            let left_text = format!("compacting{dots:<3} {}", progress_label(progress));
+            let style = Style::default().fg(GROUND_COLOR);
+            left_len = left_text.chars().count() as u16;
+            for (i, ch) in left_text.chars().enumerate() {
+                if (i as u16) < area.width {
+                    buf[(area.x + i as u16, area.y + y)].set_char(ch).set_style(style);
+                }
             }
         }
 
         // Right side: score (flash on milestone or bonus)
         let score_style = if self.game.milestone_flash > 0 {
-            // Mario star strobe: cycle through warm celebration colors
-            let phase = (self.game.milestone_flash / 2) % 5;
-            let color = match phase {
-                0 => Color::Indexed(227), // Bright yellow
-                1 => Color::Indexed(220), // Gold
-                2 => Color::Indexed(208), // Orange
-                3 => Color::Indexed(231), // White flash
-                _ => Color::Indexed(222), // Warm yellow
-            };
-            Style::default().fg(color)
+            Style::default().fg(celebration_color(self.game.milestone_flash))
         } else if self.game.score_pop > 0 {
             // Bright green pop for +10 bonus
             Style::default().fg(Color::Indexed(119)) // Bright green
@@ -148,6 +302,46 @@ impl<'a> GameScene<'a> {
                 buf[(area.x + x, area.y + y)].set_char(ch).set_style(score_style);
             }
         }
+
+        // Just left of the score once the player's own points beat the
+        // record, unless the progress text is in the way
+        let high_start = score_start.saturating_sub(NEW_HIGH.len() as u16 + 2);
+        if self.best.is_some_and(|best| self.game.player_score() > best)
+            && high_start > left_len
+        {
+            let high_style = Style::default()
+                .fg(Color::Indexed(227)) // Bright yellow, same as the milestone strobe
+                .add_modifier(Modifier::BOLD);
+            for (i, ch) in NEW_HIGH.chars().enumerate() {
+                buf[(area.x + high_start + i as u16, area.y + y)]
+                    .set_char(ch)
+                    .set_style(high_style);
+            }
+        }
+    }
+}
+
+/// Mario star strobe: cycle through warm celebration colors as `flash`
+/// counts down
+fn celebration_color(flash: u8) -> Color {
+    match (flash / 2) % 5 {
+        0 => Color::Indexed(227), // Bright yellow
+        1 => Color::Indexed(220), // Gold
+        2 => Color::Indexed(208), // Orange
+        3 => Color::Indexed(231), // White flash
+        _ => Color::Indexed(222), // Warm yellow
+    }
+}
+
+/// "~40s left" from the history estimate, or time elapsed without one
+fn progress_label(progress: ProgressStatus) -> String {
+    match progress.remaining {
+        Some(remaining) if remaining.is_zero() => "almost done".to_string(),
+        Some(remaining) if remaining.as_secs() < 60 => {
+            format!("~{}s left", remaining.as_secs().max(1))
+        }
+        Some(remaining) => format!("~{}m left", remaining.as_secs().div_ceil(60)),
+        None => format!("{}s", progress.elapsed.as_secs()),
     }
 }
 
@@ -171,6 +365,9 @@ impl Widget for GameScene<'_> {
         let ground_area = Rect::new(area.x, area.y + ground_y, area.width, 1);
         Ground::new(self.game.scroll_offset as u16).render(ground_area, buf);
 
+        // Toast sits behind the world so a jump is never hidden
+        self.render_toast(area, buf);
+
         // Render obstacles
         self.render_obstacles(area, buf);
 
@@ -179,5 +376,10 @@ impl Widget for GameScene<'_> {
 
         // Status bar is last row
         self.render_status_bar(area, buf);
+
+        // Session totals, session indicators and mode tag along the top
+        let sessions_start = self.render_sessions(area, buf);
+        self.render_session(area, buf, sessions_start);
+        self.render_tag(area, buf);
     }
 }
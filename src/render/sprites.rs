@@ -8,9 +8,6 @@ pub const CLAUDE_COLOR: Color = Color::Indexed(209);
 /// 256-color grayscale 250 ≈ light gray
 pub const OBSTACLE_COLOR: Color = Color::Indexed(250);
 
-/// Flash color for collision effect
-pub const COLLISION_COLOR: Color = Color::Indexed(196); // Bright red
-
 /// Medium gray for ground
 /// 256-color grayscale 245 ≈ medium gray
 pub const GROUND_COLOR: Color = Color::Indexed(245);
@@ -59,22 +56,49 @@ impl ClaudeSprite {
         " █▅█▅█ ",
     ];
 
+    /// Columns every sprite takes up
     pub const WIDTH: u16 = 7;
+    /// Rows of the running and jumping sprites
     pub const HEIGHT: u16 = 3;
+    /// Rows of the ducking sprites
     pub const DUCK_HEIGHT: u16 = 2;
+    /// Rows of the landing sprite
     pub const LANDING_HEIGHT: u16 = 2;
 }
 
 /// Obstacle types with their sprites
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ObstacleType {
+    /// Three rows high, the easiest to clear
     Small,
+    /// A row taller than Small
     Tall,
+    /// Two Smalls side by side, hard to clear for its width
     Double,
+    /// Flies at head height; duck under it
     Flying,
 }
 
 impl ObstacleType {
+    /// Every type, in declaration order
+    pub const ALL: [ObstacleType; 4] = [
+        ObstacleType::Small,
+        ObstacleType::Tall,
+        ObstacleType::Double,
+        ObstacleType::Flying,
+    ];
+
+    /// Lowercase name used in stats output
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObstacleType::Small => "small",
+            ObstacleType::Tall => "tall",
+            ObstacleType::Double => "double",
+            ObstacleType::Flying => "flying",
+        }
+    }
+
+    /// Rows of the sprite, top first
     pub fn sprite(&self) -> &'static [&'static str] {
         match self {
             ObstacleType::Small => &[
@@ -100,6 +124,7 @@ impl ObstacleType {
         }
     }
 
+    /// Columns the sprite takes up
     pub fn width(&self) -> u16 {
         match self {
             ObstacleType::Small => 3,
@@ -109,6 +134,7 @@ impl ObstacleType {
         }
     }
 
+    /// Rows the sprite takes up, counting up from the ground
     pub fn height(&self) -> u16 {
         match self {
             ObstacleType::Small => 3,
@@ -128,6 +154,7 @@ impl ObstacleType {
         }
     }
 
+    /// Rows of the sprite that count as a hit
     pub fn hitbox_height(&self) -> u16 {
         match self {
             ObstacleType::Small => 2,
@@ -137,11 +164,6 @@ impl ObstacleType {
         }
     }
 
-    /// Returns true if this obstacle flies above ground
-    pub fn is_flying(&self) -> bool {
-        matches!(self, ObstacleType::Flying)
-    }
-
     /// Height above ground for flying obstacles
     pub fn fly_height(&self) -> u16 {
         match self {
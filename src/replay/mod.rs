@@ -0,0 +1,31 @@
+/// Playing a recording back in the terminal
+pub mod player;
+/// The recording format and feeding it back into a game
+pub mod recording;
+
+pub use player::play;
+pub use recording::{Playback, Recording, ReplayInput};
+
+use clap::ValueEnum;
+
+/// How fast `clawd-runner replay` plays a recording back
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum ReplaySpeed {
+    /// Same pace as the original run
+    #[default]
+    Normal,
+    /// Four times as fast
+    Fast,
+    /// One tick per keypress
+    Step,
+}
+
+impl ReplaySpeed {
+    /// Game ticks per real-time tick
+    pub fn rate(self) -> f32 {
+        match self {
+            ReplaySpeed::Fast => 4.0,
+            ReplaySpeed::Normal | ReplaySpeed::Step => 1.0,
+        }
+    }
+}
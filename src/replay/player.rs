@@ -0,0 +1,79 @@
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::{Playback, Recording, ReplaySpeed};
+use crate::game::physics::TICKS_PER_SECOND;
+use crate::game::timestep::FixedTimestep;
+use crate::input::{GameAction, pending_input, poll_input, wait_for_input};
+use crate::render::{EndScreen, GameScene, InlineTerminal};
+
+/// Play a recorded run back through the same tick and render path
+pub fn play(path: &Path, speed: ReplaySpeed, grace: u16, fps: u16) -> io::Result<()> {
+    let recording = Recording::load(path)?;
+    let mut game = recording.initial_state();
+    let mut playback = Playback::new(recording);
+
+    let mut terminal = InlineTerminal::enter()?;
+    let frame_duration = Duration::from_secs(1) / u32::from(fps);
+    let grace_frames = grace.saturating_mul(TICKS_PER_SECOND as u16);
+    let mut clock = FixedTimestep::with_rate(speed.rate());
+    let mut last_frame = Instant::now();
+
+    loop {
+        let frame_start = Instant::now();
+
+        if game.should_quit || game.end_screen_done() {
+            break;
+        }
+
+        // Step mode waits for a key before every tick; otherwise take every
+        // key pressed since the last frame
+        let actions = match speed {
+            ReplaySpeed::Step if !game.is_ended() => poll_input(Duration::from_millis(100))?
+                .into_iter()
+                .collect(),
+            _ => pending_input()?,
+        };
+        let mut stepped = false;
+        for action in actions {
+            match action {
+                GameAction::Quit => game.should_quit = true,
+                _ if game.is_frozen() => game.should_quit = true,
+                _ => stepped = true,
+            }
+        }
+
+        let now = Instant::now();
+        let due = clock.advance(now - last_frame);
+        last_frame = now;
+        let stepping = speed == ReplaySpeed::Step && !game.is_ended();
+        let ticks = if stepping { u32::from(stepped) } else { due };
+        for _ in 0..ticks {
+            if playback.is_done(&game) {
+                game.finish(playback.recording().end, grace_frames, false);
+            } else {
+                playback.apply_due(&mut game);
+            }
+            game.tick();
+            game.maybe_spawn_obstacle();
+        }
+
+        terminal.draw(|frame| {
+            let area = frame.area();
+            let alpha = if stepping { 1.0 } else { clock.alpha() };
+            let scene = GameScene::new(&game)
+                .with_tag(Some("replay"))
+                .with_alpha(alpha);
+            frame.render_widget(scene, area);
+            frame.render_widget(EndScreen::new(&game), area);
+        })?;
+
+        let elapsed = frame_start.elapsed();
+        if speed != ReplaySpeed::Step && elapsed < frame_duration {
+            wait_for_input(frame_duration - elapsed)?;
+        }
+    }
+
+    terminal.leave()
+}
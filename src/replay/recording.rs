@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::{EndReason, GameState};
+
+/// Bump when the replay file layout changes
+const REPLAY_VERSION: u32 = 1;
+
+/// Something that changed game state from outside `tick()`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayInput {
+    /// The player jumped
+    Jump,
+    /// The player toggled ducking
+    Duck,
+    /// Terminal width changed, which moves where obstacles spawn
+    Resize(u16),
+}
+
+impl ReplayInput {
+    /// Make the same change to `game` the live input did
+    pub fn apply(self, game: &mut GameState) {
+        match self {
+            ReplayInput::Jump => game.jump(),
+            ReplayInput::Duck => game.toggle_duck(),
+            ReplayInput::Resize(width) => game.terminal_width = width,
+        }
+    }
+}
+
+/// A run as seed, starting parameters and inputs keyed by frame
+/// Inputs are applied before the tick for that frame, same as the live loop
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Recording {
+    version: u32,
+    /// Seed the run's obstacles came from
+    pub seed: u64,
+    /// Starting speed multiplier
+    pub speed: f32,
+    /// Play area width at the start
+    pub terminal_width: u16,
+    /// Frame the run ended on
+    pub frames: u64,
+    /// How the run ended
+    pub end: EndReason,
+    /// Every input with the frame it was applied before, in order
+    pub inputs: Vec<(u64, ReplayInput)>,
+}
+
+impl Recording {
+    /// Start recording from a fresh game's parameters
+    pub fn new(game: &GameState) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed: game.seed,
+            speed: game.speed,
+            terminal_width: game.terminal_width,
+            frames: 0,
+            end: EndReason::Quit,
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Note an input applied before the tick for `game.frame_count`
+    pub fn record(&mut self, game: &GameState, input: ReplayInput) {
+        self.inputs.push((game.frame_count, input));
+    }
+
+    /// Close out the recording once the run is over
+    pub fn finish(&mut self, game: &GameState, end: EndReason) {
+        self.frames = game.frame_count;
+        self.end = end;
+    }
+
+    /// The game as it was before the first tick
+    pub fn initial_state(&self) -> GameState {
+        let mut game = GameState::new()
+            .with_terminal_width(self.terminal_width)
+            .with_seed(self.seed);
+        game.speed = self.speed;
+        game
+    }
+
+    /// Read a recording saved with `save`
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let bytes = fs::read(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+        let recording: Self = serde_json::from_slice(&bytes).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} is not a valid recording: {err}", path.display()),
+            )
+        })?;
+        if recording.version != REPLAY_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} has replay version {}, expected {REPLAY_VERSION}",
+                    path.display(),
+                    recording.version
+                ),
+            ));
+        }
+        Ok(recording)
+    }
+
+    /// Write the recording to `path` as JSON
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_vec(self)?)
+    }
+}
+
+/// Feeds a recording's inputs back in frame order
+#[derive(Debug)]
+pub struct Playback {
+    recording: Recording,
+    next: usize,
+}
+
+impl Playback {
+    /// Start from the recording's first input
+    pub fn new(recording: Recording) -> Self {
+        Self { recording, next: 0 }
+    }
+
+    /// The recording being played
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Apply every input due before the next tick
+    pub fn apply_due(&mut self, game: &mut GameState) {
+        while let Some(&(frame, input)) = self.recording.inputs.get(self.next) {
+            if frame > game.frame_count {
+                break;
+            }
+            input.apply(game);
+            self.next += 1;
+        }
+    }
+
+    /// The run has reached the frame it ended on
+    pub fn is_done(&self, game: &GameState) -> bool {
+        game.frame_count >= self.recording.frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::GameAction;
+    use crate::runner::Runner;
+    use std::time::Duration;
+
+    #[test]
+    fn replay_reproduces_the_recorded_run() {
+        let game = GameState::new().with_terminal_width(100).with_seed(42);
+        let mut runner = Runner::new(game).with_autopilot(0.6).with_recording();
+        for call in 0..200 {
+            // A few keys from the player on top of the autopilot's
+            if call % 37 == 0 {
+                runner.feed_action(GameAction::Jump);
+            }
+            if call % 53 == 0 {
+                runner.feed_action(GameAction::ToggleDuck);
+            }
+            runner.tick(Duration::from_secs(1));
+        }
+        runner.finish(EndReason::Compaction);
+        let recording = runner.take_recording().unwrap();
+        let recorded = runner.game();
+        assert!(!recording.inputs.is_empty());
+        assert!(recorded.obstacles_cleared > 0);
+
+        let mut game = recording.initial_state();
+        let mut playback = Playback::new(recording.clone());
+        while !playback.is_done(&game) {
+            playback.apply_due(&mut game);
+            game.tick();
+            game.maybe_spawn_obstacle();
+        }
+
+        assert_eq!(game.frame_count, recorded.frame_count);
+        assert_eq!(game.score, recorded.score);
+        assert_eq!(game.collisions, recorded.collisions);
+        assert_eq!(game.obstacles_cleared, recorded.obstacles_cleared);
+        assert_eq!(game.player.y, recorded.player.y);
+        assert_eq!(recording.end, EndReason::Compaction);
+    }
+
+    #[test]
+    fn load_errors_name_the_file() {
+        let path = std::env::temp_dir().join("clawd-runner-missing-recording.json");
+        let err = Recording::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().starts_with(&path.display().to_string()));
+    }
+}
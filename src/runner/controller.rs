@@ -0,0 +1,302 @@
+use crossterm::event::{KeyEvent, KeyEventKind};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::achievements::{Achievement, Tracker};
+use crate::game::physics::TICKS_PER_SECOND;
+use crate::game::timestep::FixedTimestep;
+use crate::game::{EndReason, GameState};
+use crate::input::{AttractMode, Autopilot, GameAction, Policy, handle_key};
+use crate::replay::{Recording, ReplayInput};
+
+/// How long an achievement toast stays up
+const TOAST_FRAMES: u8 = 3 * TICKS_PER_SECOND as u8;
+
+/// Drives a game for a host app
+/// The host owns the clock and the input: it passes in elapsed time and
+/// key events, and draws with `RunnerWidget`. Nothing here reads stdin
+/// or touches the terminal.
+///
+/// ```
+/// use clawd_runner::{EndReason, GameState, Runner, RunnerWidget};
+/// use ratatui::{Terminal, backend::TestBackend};
+/// use std::time::Duration;
+///
+/// let mut runner = Runner::new(GameState::new().with_seed(1)).with_grace(1);
+/// let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+///
+/// // In the host's event loop: forward keys with `feed_key`, then
+/// runner.tick(Duration::from_millis(33));
+/// runner.resize(60);
+/// terminal
+///     .draw(|f| f.render_stateful_widget(RunnerWidget::new(), f.area(), &mut runner))
+///     .unwrap();
+///
+/// // The long operation finished: show the banner, then drop the widget
+/// runner.finish(EndReason::Compaction);
+/// for _ in 0..40 {
+///     runner.tick(Duration::from_millis(33));
+/// }
+/// assert!(runner.is_finished());
+/// ```
+#[derive(Debug)]
+pub struct Runner {
+    game: GameState,
+    clock: FixedTimestep,
+    grace_frames: u16,
+    keep_playing: bool,
+    autopilot: Option<Autopilot>,
+    attract: AttractMode,
+    recording: Option<Recording>,
+    achievements: Option<Tracker>,
+    unlocked: Vec<&'static Achievement>,
+    toasts: VecDeque<String>, // Waiting for the current toast to end
+}
+
+impl Runner {
+    /// Wrap a fresh game; the end banner shows for 3 seconds by default
+    pub fn new(game: GameState) -> Self {
+        let seed = game.seed;
+        Self {
+            game,
+            clock: FixedTimestep::new(),
+            grace_frames: 3 * TICKS_PER_SECOND as u16,
+            keep_playing: false,
+            autopilot: None,
+            attract: AttractMode::new(0, seed),
+            recording: None,
+            achievements: None,
+            unlocked: Vec::new(),
+            toasts: VecDeque::new(),
+        }
+    }
+
+    /// How long the end banner stays up after `finish`
+    pub fn with_grace(mut self, secs: u16) -> Self {
+        self.grace_frames = secs.saturating_mul(TICKS_PER_SECOND as u16);
+        self
+    }
+
+    /// Keep the world moving after `finish` until the player presses a key
+    pub fn with_keep_playing(mut self, keep_playing: bool) -> Self {
+        self.keep_playing = keep_playing;
+        self
+    }
+
+    /// Let the autopilot play the whole run
+    pub fn with_autopilot(mut self, skill: f32) -> Self {
+        self.autopilot = Some(Autopilot::new(skill, self.game.seed));
+        self.game.demo = true;
+        self
+    }
+
+    /// Hand over to the autopilot after `idle_secs` without a key (0 never does)
+    pub fn with_attract(mut self, idle_secs: u64) -> Self {
+        self.attract = AttractMode::new(idle_secs, self.game.seed);
+        self
+    }
+
+    /// Record inputs for `clawd-runner replay`
+    pub fn with_recording(mut self) -> Self {
+        self.recording = Some(Recording::new(&self.game));
+        self
+    }
+
+    /// Watch for achievements and toast them as they unlock
+    pub fn with_achievements(mut self, tracker: Tracker) -> Self {
+        self.achievements = Some(tracker);
+        self
+    }
+
+    /// The game being run, for reading its score or state
+    pub fn game(&self) -> &GameState {
+        &self.game
+    }
+
+    /// HUD label for whoever is playing, if it isn't the player
+    pub fn tag(&self) -> Option<&'static str> {
+        if self.autopilot.is_some() {
+            Some("autopilot")
+        } else {
+            self.attract.is_active().then_some("demo")
+        }
+    }
+
+    /// How far between ticks the next draw falls, for `GameScene::with_alpha`
+    pub fn alpha(&self) -> f32 {
+        self.clock.alpha()
+    }
+
+    /// Handle a key with the default key map; releases are ignored
+    pub fn feed_key(&mut self, key: KeyEvent) {
+        if key.kind == KeyEventKind::Press {
+            self.feed_action(handle_key(key));
+        }
+    }
+
+    /// Handle an action from the host's own key map
+    /// Any key takes control back from attract mode and, once the run is
+    /// over, closes the game
+    pub fn feed_action(&mut self, action: GameAction) {
+        if self.game.is_ended() {
+            self.game.should_quit = true;
+            return;
+        }
+        self.attract.on_key(&mut self.game);
+        self.apply(action);
+    }
+
+    /// Advance the game by however many fixed ticks `elapsed` adds up to
+    pub fn tick(&mut self, elapsed: Duration) {
+        for _ in 0..self.clock.advance(elapsed) {
+            if !self.game.is_frozen() {
+                let action = match self.autopilot {
+                    Some(ref mut bot) => bot.act(&self.game),
+                    None => self.attract.act(&mut self.game),
+                };
+                self.apply(action);
+            }
+            self.game.tick();
+            self.game.maybe_spawn_obstacle();
+            self.check_achievements();
+        }
+    }
+
+    /// End the run, e.g. when the operation the player was waiting on is done
+    pub fn finish(&mut self, reason: EndReason) {
+        self.game
+            .finish(reason, self.grace_frames, self.keep_playing);
+    }
+
+    /// The player quit or the end banner has run its course
+    pub fn is_finished(&self) -> bool {
+        self.game.should_quit || self.game.end_screen_done()
+    }
+
+    /// Close out and hand over the recording, if there is one
+    pub fn take_recording(&mut self) -> Option<Recording> {
+        let mut recording = self.recording.take()?;
+        let end = self.game.end_reason().unwrap_or(EndReason::Quit);
+        recording.finish(&self.game, end);
+        Some(recording)
+    }
+
+    /// Hand over what unlocked since the last call, for the host to save
+    pub fn take_unlocked(&mut self) -> Vec<&'static Achievement> {
+        std::mem::take(&mut self.unlocked)
+    }
+
+    /// Match the width of the area the widget is drawn into
+    /// Call it before drawing whenever the area may have changed, so
+    /// obstacles spawn at its right edge.
+    pub fn resize(&mut self, width: u16) {
+        if width != self.game.terminal_width {
+            self.record(ReplayInput::Resize(width));
+        }
+    }
+
+    /// Queue a toast per new unlock and show the next once the last one ends
+    fn check_achievements(&mut self) {
+        if let Some(ref mut tracker) = self.achievements {
+            for achievement in tracker.observe(&self.game) {
+                self.toasts
+                    .push_back(format!("achievement unlocked: {}", achievement.name));
+                self.unlocked.push(achievement);
+            }
+        }
+        if self.game.toast_flash == 0
+            && let Some(message) = self.toasts.pop_front()
+        {
+            self.game.show_toast(message, TOAST_FRAMES);
+        }
+    }
+
+    fn apply(&mut self, action: GameAction) {
+        match action {
+            GameAction::Jump => self.record(ReplayInput::Jump),
+            GameAction::ToggleDuck => self.record(ReplayInput::Duck),
+            GameAction::Quit => self.game.should_quit = true,
+            GameAction::None => {}
+        }
+    }
+
+    /// Apply an input, noting it in the recording if there is one
+    fn record(&mut self, input: ReplayInput) {
+        if let Some(ref mut recording) = self.recording {
+            recording.record(&self.game, input);
+        }
+        input.apply(&mut self.game);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::Obstacle;
+    use crate::game::timestep::MAX_CATCH_UP_TICKS;
+    use crate::render::sprites::ObstacleType;
+
+    /// Run exactly `calls * MAX_CATCH_UP_TICKS` ticks; a whole second per
+    /// call always hits the catch-up cap, so rounding can't add or drop one
+    fn run(runner: &mut Runner, calls: u32) {
+        for _ in 0..calls {
+            runner.tick(Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn banner_stays_up_for_the_grace_period() {
+        let mut runner = Runner::new(GameState::new().with_seed(1)).with_grace(1);
+        runner.finish(EndReason::Compaction);
+        let calls = TICKS_PER_SECOND as u32 / MAX_CATCH_UP_TICKS;
+
+        run(&mut runner, calls - 1);
+        assert!(!runner.is_finished());
+        run(&mut runner, 1);
+        assert!(runner.is_finished());
+    }
+
+    #[test]
+    fn keep_playing_moves_the_world_until_a_key() {
+        let mut runner = Runner::new(GameState::new().with_seed(1))
+            .with_grace(1)
+            .with_keep_playing(true);
+        let obstacle = Obstacle::new(40.0, ObstacleType::Small);
+        runner.game.obstacles.push(obstacle);
+        runner.finish(EndReason::Compaction);
+        let frame = runner.game().frame_count;
+
+        // Two seconds, well past the grace period
+        run(&mut runner, 12);
+        assert!(runner.game().frame_count > frame);
+        assert!(runner.game().obstacles.iter().all(|o| o.x < 40.0));
+        assert!(!runner.is_finished());
+
+        runner.feed_action(GameAction::None);
+        assert!(runner.is_finished());
+    }
+
+    #[test]
+    fn any_key_quits_once_the_run_is_over() {
+        let mut runner = Runner::new(GameState::new().with_seed(1));
+        runner.feed_action(GameAction::None);
+        assert!(!runner.is_finished());
+
+        runner.finish(EndReason::Compaction);
+        runner.feed_action(GameAction::Jump);
+        assert!(runner.is_finished());
+        assert_eq!(runner.game().player.velocity_y, 0.0);
+    }
+
+    #[test]
+    fn resize_is_recorded_once_per_change() {
+        let mut runner = Runner::new(GameState::new().with_seed(1)).with_recording();
+        runner.resize(80);
+        runner.resize(100);
+        runner.resize(100);
+        assert_eq!(runner.game().terminal_width, 100);
+
+        let recording = runner.take_recording().unwrap();
+        assert_eq!(recording.inputs, [(0, ReplayInput::Resize(100))]);
+    }
+}
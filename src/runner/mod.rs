@@ -0,0 +1,7 @@
+/// The host-driven game controller
+pub mod controller;
+/// Drawing a `Runner`
+pub mod widget;
+
+pub use controller::Runner;
+pub use widget::RunnerWidget;
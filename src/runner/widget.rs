@@ -0,0 +1,66 @@
+use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget, widgets::Widget};
+
+use super::Runner;
+use crate::render::{EndScreen, GameScene};
+use crate::storage::SessionTotals;
+use crate::watcher::{ProgressStatus, Session};
+
+/// Draws a `Runner` into any area: the scene, HUD tag and end banner
+/// Drawing only reads the runner; call `Runner::resize` with the area's
+/// width first.
+#[derive(Default)]
+pub struct RunnerWidget<'a> {
+    progress: Option<ProgressStatus>,
+    sessions: &'a [Session],
+    best: Option<u32>,
+    session: Option<SessionTotals>,
+}
+
+impl<'a> RunnerWidget<'a> {
+    /// A widget with nothing extra to show
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show compaction progress in the status bar while the run is going
+    pub fn with_progress(mut self, progress: Option<ProgressStatus>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Show one indicator per watched session
+    pub fn with_sessions(mut self, sessions: &'a [Session]) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    /// Best score from earlier runs, for the "NEW HIGH" badge
+    pub fn with_best(mut self, best: Option<u32>) -> Self {
+        self.best = best;
+        self
+    }
+
+    /// Earlier runs in the launching session, for the "session best" label
+    pub fn with_session(mut self, session: Option<SessionTotals>) -> Self {
+        self.session = session;
+        self
+    }
+}
+
+impl StatefulWidget for RunnerWidget<'_> {
+    type State = Runner;
+
+    fn render(self, area: Rect, buf: &mut Buffer, runner: &mut Runner) {
+        let game = runner.game();
+        let progress = self.progress.filter(|_| !game.is_ended());
+        GameScene::new(game)
+            .with_progress(progress)
+            .with_sessions(self.sessions)
+            .with_best(self.best)
+            .with_session(self.session)
+            .with_tag(runner.tag())
+            .with_alpha(runner.alpha())
+            .render(area, buf);
+        EndScreen::new(game).render(area, buf);
+    }
+}
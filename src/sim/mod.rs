@@ -0,0 +1,111 @@
+/// The scripted players a batch can use
+pub mod policy;
+/// Summing a batch up as JSON
+pub mod report;
+
+pub use policy::PolicyKind;
+pub use report::Report;
+
+use crate::game::GameState;
+use crate::input::GameAction;
+use crate::render::sprites::ObstacleType;
+
+/// One batch of headless runs
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    /// Number of runs
+    pub runs: u32,
+    /// Ticks per run
+    pub frames: u64,
+    /// Who plays
+    pub policy: PolicyKind,
+    /// Autopilot skill, 0.0 to 1.0
+    pub skill: f32,
+    /// Run n uses seed + n
+    pub seed: u64,
+    /// Play area width
+    pub terminal_width: u16,
+    /// Starting speed multiplier
+    pub speed: f32,
+}
+
+/// What one run left behind
+#[derive(Debug)]
+pub struct RunOutcome {
+    /// The game as it stood after the last tick
+    pub game: GameState,
+    /// Frame the first Flying obstacle spawned on
+    pub first_flying: Option<u64>,
+}
+
+/// Play every run in the batch as fast as the CPU allows
+pub fn run(config: &SimConfig) -> Report {
+    let outcomes: Vec<RunOutcome> = (0..config.runs)
+        .map(|n| run_one(config, config.seed.wrapping_add(n as u64)))
+        .collect();
+    Report::new(config, &outcomes)
+}
+
+/// Same tick/spawn order as the live loop, with the policy standing in
+/// for the keyboard
+pub fn run_one(config: &SimConfig, seed: u64) -> RunOutcome {
+    let mut game = GameState::new()
+        .with_terminal_width(config.terminal_width)
+        .with_seed(seed);
+    game.speed = config.speed;
+    let mut policy = config.policy.build(seed, config.skill);
+    let mut first_flying = None;
+
+    for _ in 0..config.frames {
+        match policy.act(&game) {
+            GameAction::Jump => game.jump(),
+            GameAction::ToggleDuck => game.toggle_duck(),
+            GameAction::Quit | GameAction::None => {}
+        }
+        game.tick();
+        game.maybe_spawn_obstacle();
+
+        if first_flying.is_none() && game.spawned_by_type.get(ObstacleType::Flying) > 0 {
+            first_flying = Some(game.frame_count);
+        }
+    }
+
+    RunOutcome { game, first_flying }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(policy: PolicyKind) -> SimConfig {
+        SimConfig {
+            runs: 3,
+            frames: 900,
+            policy,
+            skill: 0.8,
+            seed: 11,
+            terminal_width: 100,
+            speed: 1.0,
+        }
+    }
+
+    #[test]
+    fn same_seed_same_run() {
+        let config = config(PolicyKind::Random);
+        let a = run_one(&config, 5);
+        let b = run_one(&config, 5);
+        assert_eq!(a.game.score, b.game.score);
+        assert_eq!(a.game.collisions, b.game.collisions);
+        assert_eq!(a.first_flying, b.first_flying);
+        assert_eq!(a.game.frame_count, 900);
+    }
+
+    #[test]
+    fn autopilot_beats_standing_still() {
+        let idle = run(&config(PolicyKind::Idle));
+        let autopilot = run(&config(PolicyKind::Autopilot));
+        assert_eq!(idle.runs, 3);
+        assert!(idle.collisions_per_run > 0.0);
+        assert!(autopilot.collisions_per_run < idle.collisions_per_run);
+    }
+}
@@ -0,0 +1,58 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::game::GameState;
+use crate::game::rng::Rng;
+use crate::input::{Autopilot, GameAction, Policy};
+
+/// Built-in policies for `clawd-runner simulate`
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyKind {
+    /// Never presses anything
+    Idle,
+    /// Jumps and ducks at random
+    Random,
+    /// The autopilot bot at --skill
+    Autopilot,
+}
+
+impl PolicyKind {
+    /// A player of this kind, seeded so batches repeat
+    pub fn build(self, seed: u64, skill: f32) -> Box<dyn Policy> {
+        match self {
+            PolicyKind::Idle => Box::new(Idle),
+            PolicyKind::Random => Box::new(Random {
+                rng: Rng::new(seed ^ RANDOM_SALT),
+            }),
+            PolicyKind::Autopilot => Box::new(Autopilot::new(skill, seed)),
+        }
+    }
+}
+
+/// Keeps the random policy's rolls apart from the spawner's
+const RANDOM_SALT: u64 = 0x5eed_f1a9;
+
+/// Never presses anything, as a baseline
+pub struct Idle;
+
+impl Policy for Idle {
+    fn act(&mut self, _game: &GameState) -> GameAction {
+        GameAction::None
+    }
+}
+
+/// About one jump a second and a duck toggle every two
+pub struct Random {
+    rng: Rng,
+}
+
+impl Policy for Random {
+    fn act(&mut self, _game: &GameState) -> GameAction {
+        match self.rng.range(0, 60) {
+            0 | 1 => GameAction::Jump,
+            2 => GameAction::ToggleDuck,
+            _ => GameAction::None,
+        }
+    }
+}
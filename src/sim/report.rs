@@ -0,0 +1,142 @@
+use serde::Serialize;
+
+use super::{PolicyKind, RunOutcome, SimConfig};
+use crate::game::physics::TICKS_PER_SECOND;
+use crate::render::sprites::ObstacleType;
+
+/// Aggregated results of a simulation batch, printed as JSON
+#[derive(Serialize, Debug)]
+pub struct Report {
+    /// Runs in the batch
+    pub runs: u32,
+    /// Ticks per run
+    pub frames: u64,
+    /// Who played
+    pub policy: PolicyKind,
+    /// Autopilot skill, 0.0 to 1.0
+    pub skill: f32,
+    /// Seed of the first run
+    pub seed: u64,
+    /// Final scores, None for an empty batch
+    pub score: Option<Distribution>,
+    /// Average obstacles hit per run
+    pub collisions_per_run: f64,
+    /// Average obstacles cleared per run
+    pub cleared_per_run: f64,
+    /// Totals for each obstacle type, in `ObstacleType::ALL` order
+    pub obstacle_types: Vec<TypeReport>,
+    /// How soon the first flying obstacle showed up
+    pub first_flying: FirstFlying,
+}
+
+/// Summary of one number across runs
+#[derive(Serialize, Debug)]
+pub struct Distribution {
+    /// Average
+    pub mean: f64,
+    /// Smallest value
+    pub min: f64,
+    /// 10th percentile
+    pub p10: f64,
+    /// Median
+    pub p50: f64,
+    /// 90th percentile
+    pub p90: f64,
+    /// Largest value
+    pub max: f64,
+}
+
+/// Spawns and hits for one obstacle type across the batch
+#[derive(Serialize, Debug)]
+pub struct TypeReport {
+    /// `ObstacleType::name`
+    #[serde(rename = "type")]
+    pub obstacle_type: &'static str,
+    /// How many spawned
+    pub spawned: u64,
+    /// How many the player hit
+    pub collisions: u64,
+    /// Share of spawned obstacles of this type that were hit
+    pub collision_rate: Option<f64>,
+}
+
+/// Seconds of game time until the first Flying obstacle spawned
+#[derive(Serialize, Debug)]
+pub struct FirstFlying {
+    /// Runs that saw one at all
+    pub runs: u32,
+    /// When it spawned in those runs
+    pub secs: Option<Distribution>,
+}
+
+impl Report {
+    /// Sum up the batch `config` describes
+    pub fn new(config: &SimConfig, outcomes: &[RunOutcome]) -> Self {
+        let runs = outcomes.len().max(1) as f64;
+        let per_run =
+            |f: fn(&RunOutcome) -> u32| outcomes.iter().map(|o| f(o) as f64).sum::<f64>() / runs;
+
+        let obstacle_types = ObstacleType::ALL
+            .iter()
+            .map(|&t| {
+                let spawned: u64 = outcomes
+                    .iter()
+                    .map(|o| o.game.spawned_by_type.get(t) as u64)
+                    .sum();
+                let collisions: u64 = outcomes
+                    .iter()
+                    .map(|o| o.game.collisions_by_type.get(t) as u64)
+                    .sum();
+                TypeReport {
+                    obstacle_type: t.name(),
+                    spawned,
+                    collisions,
+                    collision_rate: (spawned > 0).then(|| collisions as f64 / spawned as f64),
+                }
+            })
+            .collect();
+
+        let flying: Vec<f64> = outcomes
+            .iter()
+            .filter_map(|o| o.first_flying)
+            .map(|frame| frame as f64 / TICKS_PER_SECOND as f64)
+            .collect();
+
+        Self {
+            runs: config.runs,
+            frames: config.frames,
+            policy: config.policy,
+            skill: config.skill,
+            seed: config.seed,
+            score: Distribution::of(outcomes.iter().map(|o| o.game.score as f64).collect()),
+            collisions_per_run: per_run(|o| o.game.collisions),
+            cleared_per_run: per_run(|o| o.game.obstacles_cleared),
+            obstacle_types,
+            first_flying: FirstFlying {
+                runs: flying.len() as u32,
+                secs: Distribution::of(flying),
+            },
+        }
+    }
+}
+
+impl Distribution {
+    /// None for an empty sample
+    pub fn of(mut values: Vec<f64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        // Nearest-rank percentile
+        let pct =
+            |p: f64| values[((p * values.len() as f64).ceil() as usize).clamp(1, values.len()) - 1];
+        Some(Self {
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            min: values[0],
+            p10: pct(0.10),
+            p50: pct(0.50),
+            p90: pct(0.90),
+            max: values[values.len() - 1],
+        })
+    }
+}
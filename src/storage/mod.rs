@@ -0,0 +1,132 @@
+/// Finished runs and the best score
+pub mod scores;
+/// Lifetime totals over saved runs
+pub mod stats;
+
+pub use scores::{RunRecord, ScoreBook, SessionTotals};
+pub use stats::{Stats, TypeStats};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// `$XDG_DATA_HOME/clawd-runner`, defaulting to `~/.local/share/clawd-runner`
+pub fn data_dir() -> io::Result<PathBuf> {
+    let base = match std::env::var_os("XDG_DATA_HOME").filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let home = std::env::var_os("HOME")
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME is not set"))?;
+            PathBuf::from(home).join(".local").join("share")
+        }
+    };
+    Ok(base.join("clawd-runner"))
+}
+
+/// Replace `path` with `contents` via a temp file and rename, so readers
+/// see either the old file or the new one, never half of each
+/// Both the file and the rename are flushed to disk before returning, so a
+/// crash can't leave a truncated file behind that we'd refuse to overwrite.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+    let mut tmp = path.as_os_str().to_os_string();
+    tmp.push(format!(".tmp{}", std::process::id()));
+    let tmp = PathBuf::from(tmp);
+
+    let written = File::create(&tmp)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written?;
+    // The rename only survives a crash once the directory is on disk too
+    File::open(dir)?.sync_all()
+}
+
+/// Run `f` holding an exclusive lock on `<path>.lock`
+/// The data file itself is replaced by `write_atomic`, so the lock lives
+/// beside it. Blocks until any other process holding it lets go.
+pub fn with_lock<T>(path: &Path, f: impl FnOnce() -> io::Result<T>) -> io::Result<T> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut lock_path = path.as_os_str().to_os_string();
+    lock_path.push(".lock");
+    let lock = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(PathBuf::from(lock_path))?;
+    lock.lock()?;
+    // Closing the file releases the lock, even if `f` panics
+    f()
+}
+
+/// A JSON file in the data dir that records its layout version
+pub trait Versioned: Serialize + DeserializeOwned + Default {
+    /// Bump when the file layout changes
+    const VERSION: u32;
+    /// A file that can't be read is started over instead of being an
+    /// error; only for data that's safe to lose
+    const DISPOSABLE: bool = false;
+
+    /// The version the file was written with
+    fn version(&self) -> u32;
+}
+
+/// Read a versioned file, treating a missing one as empty
+/// A file we can't parse, or one from another version, is an error
+/// rather than empty so that saving never overwrites it.
+pub fn read_versioned<T: Versioned>(path: &Path) -> io::Result<T> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(err) => return Err(err),
+    };
+    let data: T = serde_json::from_slice(&bytes).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not valid: {err}", path.display()),
+        )
+    })?;
+    if data.version() != T::VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{} has version {}, expected {}",
+                path.display(),
+                data.version(),
+                T::VERSION
+            ),
+        ));
+    }
+    Ok(data)
+}
+
+/// Read, change and write back a versioned file
+/// Runs under `with_lock` so runners finishing at the same time don't
+/// lose each other's changes.
+pub fn update_versioned<T: Versioned, R>(
+    path: &Path,
+    f: impl FnOnce(&mut T) -> R,
+) -> io::Result<R> {
+    with_lock(path, || {
+        let mut data = match read_versioned(path) {
+            Err(err) if T::DISPOSABLE && err.kind() == io::ErrorKind::InvalidData => T::default(),
+            result => result?,
+        };
+        let out = f(&mut data);
+        write_atomic(path, &serde_json::to_vec_pretty(&data)?)?;
+        Ok(out)
+    })
+}
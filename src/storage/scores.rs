@@ -0,0 +1,325 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{Versioned, read_versioned, update_versioned};
+use crate::game::state::TypeCounts;
+use crate::game::{EndReason, GameState};
+use crate::render::sprites::ObstacleType;
+
+/// One finished run
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunRecord {
+    /// Points the player scored, not counting autopilot stretches
+    pub score: u32,
+    /// Game time in seconds
+    pub duration_secs: u64,
+    /// Obstacles hit
+    pub collisions: u32,
+    /// Obstacles cleared
+    pub cleared: u32,
+    /// Seed the run's obstacles came from
+    pub seed: u64,
+    /// When the run ended, in Unix seconds
+    pub ended_at: u64,
+    /// How it ended
+    pub end: EndReason,
+    /// Transcript directory of the session that launched the game,
+    /// None in demo mode
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Claude Code session that launched the game, None in demo mode
+    #[serde(default)]
+    pub session: Option<String>,
+    /// Wall-clock seconds until the run ended, None if nothing was compacting
+    #[serde(default)]
+    pub waited_secs: Option<u64>,
+    /// Obstacles spawned, keyed by `ObstacleType::name`
+    #[serde(default)]
+    pub spawned_by_type: BTreeMap<String, u32>,
+    /// Obstacles hit, keyed the same way
+    #[serde(default)]
+    pub collisions_by_type: BTreeMap<String, u32>,
+}
+
+impl RunRecord {
+    /// Summarize a game that just ended
+    pub fn from_game(game: &GameState, end: EndReason, ended_at: SystemTime) -> Self {
+        Self {
+            score: game.player_score(),
+            duration_secs: game.elapsed_secs(),
+            collisions: game.collisions,
+            cleared: game.obstacles_cleared,
+            seed: game.seed,
+            ended_at: ended_at
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            end,
+            project: None,
+            session: None,
+            waited_secs: None,
+            spawned_by_type: by_name(game.spawned_by_type),
+            collisions_by_type: by_name(game.collisions_by_type),
+        }
+    }
+
+    /// The project whose compaction the run was waiting on
+    pub fn with_project(mut self, project: Option<String>) -> Self {
+        self.project = project;
+        self
+    }
+
+    /// The Claude Code session whose compaction the run was waiting on
+    pub fn with_session(mut self, session: Option<String>) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// How long the player waited on compaction
+    pub fn with_waited(mut self, waited: Option<Duration>) -> Self {
+        self.waited_secs = waited.map(|d| d.as_secs());
+        self
+    }
+}
+
+#[cfg(test)]
+impl RunRecord {
+    /// A run outside any session, with one hit, for tests to build on
+    pub(crate) fn sample(score: u32) -> Self {
+        Self {
+            score,
+            duration_secs: 30,
+            collisions: 1,
+            cleared: 4,
+            seed: 7,
+            ended_at: 1_700_000_000,
+            end: EndReason::Compaction,
+            project: None,
+            session: None,
+            waited_secs: None,
+            spawned_by_type: BTreeMap::new(),
+            collisions_by_type: BTreeMap::new(),
+        }
+    }
+}
+
+/// Earlier runs in one Claude Code session, which can compact many times
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SessionTotals {
+    /// Runs the session has played
+    pub runs: u32,
+    /// Best of them
+    pub best: u32,
+    /// Game time across the session's runs
+    pub play_secs: u64,
+}
+
+fn by_name(counts: TypeCounts) -> BTreeMap<String, u32> {
+    ObstacleType::ALL
+        .iter()
+        .map(|&t| (t.name().to_string(), counts.get(t)))
+        .collect()
+}
+
+/// Best score and every run so far, stored under the data dir
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ScoreBook {
+    version: u32,
+    best: u32,
+    runs: Vec<RunRecord>,
+}
+
+impl Default for ScoreBook {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            best: 0,
+            runs: Vec::new(),
+        }
+    }
+}
+
+impl ScoreBook {
+    /// `scores.json` in the data dir
+    pub fn path() -> io::Result<PathBuf> {
+        Ok(super::data_dir()?.join("scores.json"))
+    }
+
+    /// Load saved scores for display; a missing or unreadable file starts empty
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| Self::read(&path))
+            .unwrap_or_default()
+    }
+
+    /// Read a scores file, treating a missing one as empty and refusing
+    /// one we can't parse
+    pub fn read(path: &Path) -> io::Result<Self> {
+        read_versioned(path)
+    }
+
+    /// Highest score of any run, None before the first one
+    pub fn best(&self) -> Option<u32> {
+        (!self.runs.is_empty()).then_some(self.best)
+    }
+
+    /// Every run, oldest first
+    pub fn runs(&self) -> &[RunRecord] {
+        &self.runs
+    }
+
+    /// Runs that were waiting on a compaction
+    pub fn compactions(&self) -> u32 {
+        self.runs.iter().filter(|r| r.waited_secs.is_some()).count() as u32
+    }
+
+    /// Totals for a session's earlier runs, None if it has none
+    pub fn session(&self, session_id: &str) -> Option<SessionTotals> {
+        let mut totals = SessionTotals::default();
+        for run in self
+            .runs
+            .iter()
+            .filter(|r| r.session.as_deref() == Some(session_id))
+        {
+            totals.runs += 1;
+            totals.best = totals.best.max(run.score);
+            totals.play_secs += run.duration_secs;
+        }
+        (totals.runs > 0).then_some(totals)
+    }
+
+    /// Add a run; returns true if it beat the previous best
+    pub fn add(&mut self, run: RunRecord) -> bool {
+        let new_high = self.best().is_some_and(|best| run.score > best);
+        self.best = self.best.max(run.score);
+        self.runs.push(run);
+        new_high
+    }
+
+    /// Add a run to the scores file at `path`
+    /// Returns true if it beat the previous best.
+    pub fn record(path: &Path, run: RunRecord) -> io::Result<bool> {
+        update_versioned(path, |book: &mut Self| book.add(run))
+    }
+}
+
+impl Versioned for ScoreBook {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::thread;
+
+    /// A scores file in a temp dir of its own, removed along with its lock
+    /// file when dropped
+    struct TempBook(PathBuf);
+
+    impl TempBook {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("clawd-runner-test-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir.join("scores.json"))
+        }
+    }
+
+    impl Drop for TempBook {
+        fn drop(&mut self) {
+            if let Some(dir) = self.0.parent() {
+                let _ = fs::remove_dir_all(dir);
+            }
+        }
+    }
+
+    #[test]
+    fn first_run_is_not_a_new_high() {
+        let mut book = ScoreBook::default();
+        assert_eq!(book.best(), None);
+        assert!(!book.add(RunRecord::sample(50)));
+        assert_eq!(book.best(), Some(50));
+        assert!(!book.add(RunRecord::sample(50)));
+        assert!(book.add(RunRecord::sample(51)));
+        assert!(!book.add(RunRecord::sample(10)));
+        assert_eq!(book.best(), Some(51));
+    }
+
+    #[test]
+    fn session_totals_only_count_that_session() {
+        let mut book = ScoreBook::default();
+        let in_session = |score| RunRecord {
+            session: Some("abc".to_string()),
+            ..RunRecord::sample(score)
+        };
+        book.add(in_session(40));
+        book.add(RunRecord::sample(500));
+        book.add(in_session(90));
+
+        let totals = book.session("abc").unwrap();
+        assert_eq!(
+            totals,
+            SessionTotals {
+                runs: 2,
+                best: 90,
+                play_secs: 60
+            }
+        );
+        assert_eq!(book.session("other"), None);
+    }
+
+    #[test]
+    fn record_round_trips_through_the_file() {
+        let file = TempBook::new("round_trip");
+        let path = file.0.as_path();
+        assert!(!ScoreBook::record(path, RunRecord::sample(20)).unwrap());
+        assert!(ScoreBook::record(path, RunRecord::sample(30)).unwrap());
+
+        let book = ScoreBook::read(path).unwrap();
+        assert_eq!(book.best(), Some(30));
+        assert_eq!(book.runs(), &[RunRecord::sample(20), RunRecord::sample(30)]);
+    }
+
+    #[test]
+    fn unreadable_file_is_left_alone() {
+        let file = TempBook::new("garbage");
+        let path = file.0.as_path();
+        fs::write(path, "not json").unwrap();
+        assert!(ScoreBook::record(path, RunRecord::sample(1)).is_err());
+        assert_eq!(fs::read_to_string(path).unwrap(), "not json");
+
+        fs::write(path, r#"{"version": 99, "best": 5, "runs": []}"#).unwrap();
+        assert!(ScoreBook::record(path, RunRecord::sample(1)).is_err());
+    }
+
+    #[test]
+    fn concurrent_runners_keep_every_run() {
+        let file = TempBook::new("concurrent");
+        let path = file.0.as_path();
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.to_path_buf();
+                thread::spawn(move || {
+                    for j in 0..5 {
+                        ScoreBook::record(&path, RunRecord::sample(i * 10 + j)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let book = ScoreBook::read(path).unwrap();
+        assert_eq!(book.runs().len(), 40);
+        assert_eq!(book.best(), Some(74));
+    }
+}
@@ -0,0 +1,257 @@
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::scores::{RunRecord, ScoreBook};
+use crate::render::sprites::ObstacleType;
+
+/// Lifetime totals over the stored run history, for `clawd-runner stats`
+#[derive(Serialize, Debug)]
+pub struct Stats {
+    /// Runs saved
+    pub runs: u32,
+    /// Game time across all runs
+    pub play_secs: u64,
+    /// Runs that were waiting on a compaction
+    pub compactions: u32,
+    /// Wall-clock time spent waiting on those compactions
+    pub wait_secs: u64,
+    /// Best score, None before the first run
+    pub best: Option<u32>,
+    /// Mean score, None before the first run
+    pub average: Option<f64>,
+    /// Spawns and hits for each obstacle type
+    pub obstacle_types: Vec<TypeStats>,
+    /// Most played first; demo runs have no project and aren't listed
+    pub projects: Vec<ProjectStats>,
+}
+
+/// Spawns and hits for one obstacle type across every run
+#[derive(Serialize, Debug)]
+pub struct TypeStats {
+    /// `ObstacleType::name`
+    #[serde(rename = "type")]
+    pub obstacle_type: &'static str,
+    /// How many spawned
+    pub spawned: u64,
+    /// How many the player hit
+    pub collisions: u64,
+    /// Share of them that were hit, None if none spawned
+    pub collision_rate: Option<f64>,
+}
+
+/// Totals for runs launched from one project's sessions
+#[derive(Serialize, Debug)]
+pub struct ProjectStats {
+    /// Directory the project's transcripts live in
+    pub project: String,
+    /// Runs launched from it
+    pub runs: u32,
+    /// Time those runs spent waiting on compaction
+    pub wait_secs: u64,
+    /// Best score among them
+    pub best: Option<u32>,
+    /// Mean score among them
+    pub average: Option<f64>,
+}
+
+impl Stats {
+    /// Add up every run in `book`
+    pub fn new(book: &ScoreBook) -> Self {
+        let runs = book.runs();
+
+        let obstacle_types = ObstacleType::ALL
+            .iter()
+            .map(|&t| {
+                let count = |by_type: fn(&RunRecord) -> &BTreeMap<String, u32>| -> u64 {
+                    runs.iter()
+                        .filter_map(|r| by_type(r).get(t.name()))
+                        .map(|&n| n as u64)
+                        .sum()
+                };
+                let spawned = count(|r| &r.spawned_by_type);
+                let collisions = count(|r| &r.collisions_by_type);
+                TypeStats {
+                    obstacle_type: t.name(),
+                    spawned,
+                    collisions,
+                    collision_rate: (spawned > 0).then(|| collisions as f64 / spawned as f64),
+                }
+            })
+            .collect();
+
+        let mut by_project: BTreeMap<&str, Vec<&RunRecord>> = BTreeMap::new();
+        for run in runs {
+            if let Some(ref project) = run.project {
+                by_project.entry(project).or_default().push(run);
+            }
+        }
+        let mut projects: Vec<ProjectStats> = by_project
+            .into_iter()
+            .map(|(project, runs)| ProjectStats {
+                project: project.to_string(),
+                runs: runs.len() as u32,
+                wait_secs: wait_secs(runs.iter().copied()),
+                best: runs.iter().map(|r| r.score).max(),
+                average: average(runs.iter().copied()),
+            })
+            .collect();
+        // Stable sort keeps ties in name order
+        projects.sort_by_key(|p| Reverse(p.runs));
+
+        Self {
+            runs: runs.len() as u32,
+            play_secs: runs.iter().map(|r| r.duration_secs).sum(),
+            compactions: book.compactions(),
+            wait_secs: wait_secs(runs.iter()),
+            best: book.best(),
+            average: average(runs.iter()),
+            obstacle_types,
+            projects,
+        }
+    }
+}
+
+fn wait_secs<'a>(runs: impl Iterator<Item = &'a RunRecord>) -> u64 {
+    runs.filter_map(|r| r.waited_secs).sum()
+}
+
+fn average<'a>(runs: impl ExactSizeIterator<Item = &'a RunRecord>) -> Option<f64> {
+    let n = runs.len();
+    let total: u64 = runs.map(|r| r.score as u64).sum();
+    (n > 0).then(|| total as f64 / n as f64)
+}
+
+/// "1h05m", "4m10s" or "12s"
+fn format_secs(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+fn format_score(score: Option<f64>) -> String {
+    score.map_or_else(|| "-".to_string(), |s| format!("{s:.0}"))
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.runs == 0 {
+            return writeln!(f, "No runs yet");
+        }
+
+        writeln!(
+            f,
+            "{} runs · {} played · {} waiting on {} compactions",
+            self.runs,
+            format_secs(self.play_secs),
+            format_secs(self.wait_secs),
+            self.compactions
+        )?;
+        writeln!(
+            f,
+            "best {} · average {}",
+            format_score(self.best.map(f64::from)),
+            format_score(self.average)
+        )?;
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<10} {:>7} {:>6} {:>6}",
+            "obstacle", "seen", "hits", "rate"
+        )?;
+        for t in &self.obstacle_types {
+            let rate = t
+                .collision_rate
+                .map_or_else(|| "-".to_string(), |r| format!("{:.1}%", r * 100.0));
+            writeln!(
+                f,
+                "{:<10} {:>7} {:>6} {:>6}",
+                t.obstacle_type, t.spawned, t.collisions, rate
+            )?;
+        }
+
+        if self.projects.is_empty() {
+            return Ok(());
+        }
+        let width = self
+            .projects
+            .iter()
+            .map(|p| p.project.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("project".len());
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<width$} {:>5} {:>7} {:>6} {:>6}",
+            "project", "runs", "waited", "best", "avg"
+        )?;
+        for p in &self.projects {
+            writeln!(
+                f,
+                "{:<width$} {:>5} {:>7} {:>6} {:>6}",
+                p.project,
+                p.runs,
+                format_secs(p.wait_secs),
+                format_score(p.best.map(f64::from)),
+                format_score(p.average)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(score: u32, project: Option<&str>, waited: Option<u64>, tall: (u32, u32)) -> RunRecord {
+        RunRecord {
+            collisions: tall.1,
+            project: project.map(str::to_string),
+            waited_secs: waited,
+            spawned_by_type: BTreeMap::from([("tall".to_string(), tall.0)]),
+            collisions_by_type: BTreeMap::from([("tall".to_string(), tall.1)]),
+            ..RunRecord::sample(score)
+        }
+    }
+
+    #[test]
+    fn totals_and_projects() {
+        let mut book = ScoreBook::default();
+        book.add(run(100, Some("-a"), Some(40), (4, 1)));
+        book.add(run(300, Some("-b"), Some(90), (2, 0)));
+        book.add(run(200, Some("-b"), Some(70), (2, 1)));
+        book.add(run(50, None, None, (0, 0)));
+        let stats = Stats::new(&book);
+
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.compactions, 3);
+        assert_eq!(stats.wait_secs, 200);
+        assert_eq!(stats.best, Some(300));
+        assert_eq!(stats.average, Some(162.5));
+
+        let tall = &stats.obstacle_types[ObstacleType::Tall as usize];
+        assert_eq!((tall.spawned, tall.collisions), (8, 2));
+        assert_eq!(tall.collision_rate, Some(0.25));
+        let small = &stats.obstacle_types[ObstacleType::Small as usize];
+        assert_eq!(small.collision_rate, None);
+
+        let projects: Vec<_> = stats
+            .projects
+            .iter()
+            .map(|p| (p.project.as_str(), p.runs, p.wait_secs, p.best, p.average))
+            .collect();
+        assert_eq!(
+            projects,
+            [
+                ("-b", 2, 160, Some(300), Some(250.0)),
+                ("-a", 1, 40, Some(100), Some(100.0))
+            ]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use inotify::{Inotify, WatchMask};
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use super::{TranscriptWatcher, WatchEvent, poll};
+
+/// An inotify watch on the directory holding the transcript
+/// Watching the directory rather than the file means we still see the
+/// transcript after it's replaced by a rename (atomic write)
+pub struct DirWatch {
+    inotify: Inotify,
+    file_name: OsString,
+}
+
+impl DirWatch {
+    /// Watch the directory `transcript` is in
+    pub fn new(transcript: &Path) -> io::Result<Self> {
+        let file_name = transcript
+            .file_name()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "transcript path has no file name",
+                )
+            })?
+            .to_os_string();
+        let dir = match transcript.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+
+        let inotify = Inotify::init()?;
+        inotify.watches().add(
+            dir,
+            WatchMask::MODIFY | WatchMask::CLOSE_WRITE | WatchMask::CREATE | WatchMask::MOVED_TO,
+        )?;
+
+        Ok(Self { inotify, file_name })
+    }
+
+    /// Block on inotify events in a background thread, re-reading the
+    /// transcript whenever it's touched; falls back to polling on a read error
+    pub fn spawn(mut self, mut watcher: TranscriptWatcher, session: String, tx: Sender<WatchEvent>) {
+        thread::spawn(move || {
+            let mut buffer = [0u8; 4096];
+
+            // Catch anything written between opening the transcript and adding the watch
+            let mut touched = true;
+            loop {
+                // A missing file means we're mid-replace; the MOVED_TO/CREATE event follows
+                if touched && watcher.compaction_finished().unwrap_or(false) {
+                    let _ = tx.send(WatchEvent::CompactionComplete(session));
+                    return;
+                }
+
+                // If inotify stops working, keep watching by polling instead
+                let Ok(events) = self.inotify.read_events_blocking(&mut buffer) else {
+                    poll::spawn(watcher, session, tx);
+                    return;
+                };
+                touched = events
+                    .into_iter()
+                    .any(|event| event.name == Some(self.file_name.as_os_str()));
+            }
+        });
+    }
+}
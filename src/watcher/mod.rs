@@ -1,3 +1,83 @@
+/// Waiting on transcript changes with inotify
+#[cfg(target_os = "linux")]
+pub mod inotify;
+/// Waiting on transcript changes by polling
+pub mod poll;
+/// Finding and watching the Claude Code process
+pub mod process;
+/// Estimating how long compaction will take
+pub mod progress;
+/// The sessions a runner waits on
+pub mod sessions;
+/// Spotting the compaction boundary in a transcript
 pub mod transcript;
+/// Everything a run waits on, in one place
+pub mod waiter;
 
+pub use progress::{CompactionHistory, CompactionProgress, ProgressStatus};
+pub use sessions::{EndWhen, Session, SessionSet};
 pub use transcript::TranscriptWatcher;
+pub use waiter::Waiter;
+
+use clap::ValueEnum;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+/// Events pushed from the watcher threads into the game loop
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    /// The session's compaction finished
+    CompactionComplete(String),
+    /// The watched Claude Code process is gone
+    ProcessExited(u32),
+    /// Another session started compacting and asked this runner to watch it
+    SessionJoined {
+        /// The session's id
+        session_id: String,
+        /// Short name for the HUD
+        label: String,
+        /// Its transcript, if the hook payload had one
+        transcript: Option<PathBuf>,
+    },
+}
+
+/// How the watcher thread waits for transcript changes
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum Backend {
+    /// inotify where available, polling otherwise
+    #[default]
+    Auto,
+    /// Linux inotify on the transcript's directory
+    Inotify,
+    /// Re-read the transcript on a fixed interval
+    Poll,
+}
+
+/// Move the watcher onto its own thread
+/// `tx` receives a single `CompactionComplete(session)` once compaction completes
+pub fn spawn(
+    watcher: TranscriptWatcher,
+    session: String,
+    backend: Backend,
+    tx: Sender<WatchEvent>,
+) -> io::Result<()> {
+    match backend {
+        #[cfg(target_os = "linux")]
+        Backend::Auto | Backend::Inotify => match inotify::DirWatch::new(watcher.path()) {
+            Ok(dir) => dir.spawn(watcher, session, tx),
+            Err(err) if backend == Backend::Inotify => return Err(err),
+            Err(_) => poll::spawn(watcher, session, tx),
+        },
+        #[cfg(not(target_os = "linux"))]
+        Backend::Inotify => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "inotify is only available on Linux",
+            ));
+        }
+        _ => poll::spawn(watcher, session, tx),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+use super::{TranscriptWatcher, WatchEvent};
+
+/// How often to re-read the transcript
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Fallback backend: re-read the transcript on a fixed interval
+pub fn spawn(mut watcher: TranscriptWatcher, session: String, tx: Sender<WatchEvent>) {
+    thread::spawn(move || {
+        loop {
+            if watcher.compaction_finished().unwrap_or(false) {
+                let _ = tx.send(WatchEvent::CompactionComplete(session));
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
@@ -0,0 +1,84 @@
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+use super::WatchEvent;
+
+/// How often to check whether the process is still alive
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How far up the process tree to look for Claude Code
+const MAX_ANCESTORS: usize = 8;
+
+/// Send `ProcessExited` once `pid` is gone
+pub fn spawn(pid: u32, tx: Sender<WatchEvent>) {
+    thread::spawn(move || {
+        while is_alive(pid) {
+            thread::sleep(POLL_INTERVAL);
+        }
+        let _ = tx.send(WatchEvent::ProcessExited(pid));
+    });
+}
+
+/// The Claude Code process that ran our hook
+/// Hooks run via a shell, so walk up from our parent looking for `claude`
+/// (or the node process it runs as). None if there's no such ancestor,
+/// which is always the case where we can't read the process tree.
+pub fn find_claude() -> Option<u32> {
+    let mut pid = std::os::unix::process::parent_id();
+    for _ in 0..MAX_ANCESTORS {
+        let Some((name, ppid)) = process_info(pid) else {
+            break;
+        };
+        if name == "claude" || name.starts_with("node") {
+            return Some(pid);
+        }
+        if ppid <= 1 {
+            break;
+        }
+        pid = ppid;
+    }
+    None
+}
+
+/// Name and parent pid from /proc/<pid>/stat
+#[cfg(target_os = "linux")]
+fn process_info(pid: u32) -> Option<(String, u32)> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // Format is `pid (comm) state ppid ...`; comm may itself contain ") "
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let name = stat[open + 1..close].to_string();
+    let mut rest = stat[close + 1..].split_whitespace();
+    let _state = rest.next()?;
+    let ppid = rest.next()?.parse().ok()?;
+    Some((name, ppid))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_info(_pid: u32) -> Option<(String, u32)> {
+    None
+}
+
+/// A zombie counts as dead: it has exited, nobody has reaped it yet
+#[cfg(target_os = "linux")]
+fn is_alive(pid: u32) -> bool {
+    let Ok(stat) = std::fs::read_to_string(format!("/proc/{pid}/stat")) else {
+        return false;
+    };
+    let state = stat
+        .rfind(')')
+        .and_then(|close| stat[close + 1..].split_whitespace().next());
+    !matches!(state, Some("Z") | Some("X") | None)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_alive(pid: u32) -> bool {
+    // Signal 0 checks existence without sending anything; EPERM means it
+    // exists but belongs to someone else
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    unsafe { libc::kill(pid, 0) == 0 }
+    || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
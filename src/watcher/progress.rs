@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::storage::{self, Versioned, read_versioned, update_versioned};
+
+/// Keep only the most recent compactions
+const MAX_SAMPLES: usize = 50;
+
+/// How many past compactions of similar size feed an estimate
+const NEAREST_SAMPLES: usize = 5;
+
+/// One finished compaction
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Sample {
+    /// Transcript size in bytes when compaction started
+    pub size: u64,
+    /// How long until the boundary record appeared
+    pub secs: f32,
+}
+
+/// Past compaction timings, stored under the data dir
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CompactionHistory {
+    version: u32,
+    samples: Vec<Sample>,
+}
+
+impl Default for CompactionHistory {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl CompactionHistory {
+    /// `compactions.json` in the data dir
+    pub fn path() -> io::Result<PathBuf> {
+        Ok(storage::data_dir()?.join("compactions.json"))
+    }
+
+    /// Load saved history; missing or unreadable history starts empty
+    pub fn load() -> Self {
+        Self::path()
+            .map(|path| Self::read(&path))
+            .unwrap_or_default()
+    }
+
+    /// Read a history file; unlike scores, a bad one is only timings and
+    /// is safe to start over
+    pub fn read(path: &Path) -> Self {
+        read_versioned(path).unwrap_or_default()
+    }
+
+    /// Add a finished compaction, dropping the oldest past `MAX_SAMPLES`
+    pub fn add(&mut self, size: u64, elapsed: Duration) {
+        self.samples.push(Sample {
+            size,
+            secs: elapsed.as_secs_f32(),
+        });
+        if self.samples.len() > MAX_SAMPLES {
+            self.samples.remove(0);
+        }
+    }
+
+    /// Add a compaction to the history file at `path` under a lock, like
+    /// `ScoreBook::record`, so concurrent games don't drop each other's
+    pub fn record(path: &Path, size: u64, elapsed: Duration) -> io::Result<()> {
+        update_versioned(path, |history: &mut Self| history.add(size, elapsed))
+    }
+
+    /// Expected duration for a transcript of `size` bytes
+    /// Takes the few past compactions closest in size, scales each
+    /// linearly to `size`, and returns the median
+    pub fn estimate(&self, size: u64) -> Option<Duration> {
+        let size = size.max(1) as f32;
+        let mut nearest: Vec<&Sample> = self.samples.iter().filter(|s| s.size > 0).collect();
+        // Compare sizes by ratio, not difference: 1MB vs 2MB is as far apart as 10MB vs 20MB
+        nearest.sort_by(|a, b| {
+            let da = (a.size as f32 / size).ln().abs();
+            let db = (b.size as f32 / size).ln().abs();
+            da.total_cmp(&db)
+        });
+        nearest.truncate(NEAREST_SAMPLES);
+
+        let mut scaled: Vec<f32> = nearest
+            .iter()
+            .map(|s| s.secs * size / s.size as f32)
+            .collect();
+        if scaled.is_empty() {
+            return None;
+        }
+        scaled.sort_by(f32::total_cmp);
+        Some(Duration::from_secs_f32(scaled[scaled.len() / 2]))
+    }
+}
+
+impl Versioned for CompactionHistory {
+    const VERSION: u32 = 1;
+    const DISPOSABLE: bool = true;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+/// Progress of the compaction we're waiting on
+#[derive(Debug)]
+pub struct CompactionProgress {
+    started: Instant,
+    transcript_size: u64,
+    estimate: Option<Duration>,
+}
+
+/// What the status bar shows for one frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressStatus {
+    /// Time since compaction started
+    pub elapsed: Duration,
+    /// None without history to estimate from
+    pub remaining: Option<Duration>,
+}
+
+impl CompactionProgress {
+    /// Compaction of a `transcript_size`-byte transcript that began at
+    /// `started`, estimated from `history`
+    pub fn new(started: Instant, transcript_size: u64, history: &CompactionHistory) -> Self {
+        Self {
+            started,
+            transcript_size,
+            estimate: history.estimate(transcript_size),
+        }
+    }
+
+    /// Transcript size when compaction started, in bytes
+    pub fn transcript_size(&self) -> u64 {
+        self.transcript_size
+    }
+
+    /// How long compaction has been running at `now`
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.started)
+    }
+
+    /// Elapsed and remaining time at `now`
+    pub fn status(&self, now: Instant) -> ProgressStatus {
+        let elapsed = self.elapsed(now);
+        ProgressStatus {
+            elapsed,
+            remaining: self.estimate.map(|e| e.saturating_sub(elapsed)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(samples: &[(u64, f32)]) -> CompactionHistory {
+        let mut history = CompactionHistory::default();
+        for &(size, secs) in samples {
+            history.add(size, Duration::from_secs_f32(secs));
+        }
+        history
+    }
+
+    fn secs(estimate: Option<Duration>) -> Option<f32> {
+        estimate.map(|d| (d.as_secs_f32() * 100.0).round() / 100.0)
+    }
+
+    #[test]
+    fn no_usable_samples_means_no_estimate() {
+        assert_eq!(history(&[]).estimate(1000), None);
+        assert_eq!(history(&[(0, 30.0)]).estimate(1000), None);
+    }
+
+    #[test]
+    fn single_sample_scales_with_size() {
+        let history = history(&[(1000, 10.0)]);
+        assert_eq!(secs(history.estimate(1000)), Some(10.0));
+        assert_eq!(secs(history.estimate(3000)), Some(30.0));
+    }
+
+    #[test]
+    fn median_ignores_an_outlier() {
+        let history = history(&[
+            (1000, 1.0),
+            (1000, 2.0),
+            (1000, 3.0),
+            (1000, 4.0),
+            (1000, 50.0),
+        ]);
+        assert_eq!(secs(history.estimate(1000)), Some(3.0));
+    }
+
+    #[test]
+    fn only_the_nearest_sizes_count() {
+        // Six far-off samples would outvote five close ones if all counted
+        let mut samples = vec![(1000, 10.0); NEAREST_SAMPLES];
+        samples.extend([(100_000, 10_000.0); NEAREST_SAMPLES + 1]);
+        assert_eq!(secs(history(&samples).estimate(1200)), Some(12.0));
+
+        // Nearness is by ratio: 1800 bytes is closer to 1000 than 500 is,
+        // though further off in bytes
+        let mut samples = vec![(500, 5.0); NEAREST_SAMPLES];
+        samples.extend([(1800, 36.0); NEAREST_SAMPLES]);
+        assert_eq!(secs(history(&samples).estimate(1000)), Some(20.0));
+    }
+
+    #[test]
+    fn oldest_samples_drop_off() {
+        let mut samples = vec![(1000, 100.0)];
+        samples.extend([(1000, 10.0); MAX_SAMPLES]);
+        let history = history(&samples);
+        assert_eq!(history.samples.len(), MAX_SAMPLES);
+        assert_eq!(secs(history.estimate(1000)), Some(10.0));
+    }
+
+    #[test]
+    fn record_starts_over_from_a_bad_file() {
+        let dir =
+            std::env::temp_dir().join(format!("clawd-runner-test-{}-history", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("compactions.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        CompactionHistory::record(&path, 1000, Duration::from_secs(10)).unwrap();
+        let history = CompactionHistory::read(&path);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(secs(history.estimate(1000)), Some(10.0));
+    }
+}
@@ -0,0 +1,162 @@
+use clap::ValueEnum;
+use std::path::Path;
+
+/// Which sessions have to finish compacting before the game ends
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum EndWhen {
+    /// The session whose hook launched the game, plus any that joined it
+    /// (their hooks have already returned, so nothing else will show them)
+    #[default]
+    Launcher,
+    /// Every watched session
+    All,
+}
+
+/// A Claude Code session we're waiting on
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    /// Claude Code's session id
+    pub id: String,
+    /// Short name for the HUD, usually the project directory
+    pub label: String,
+    /// Its compaction has finished
+    pub done: bool,
+    /// Handed over by another session's hook rather than given at startup
+    pub joined: bool,
+}
+
+/// Sessions compacting while this runner is up
+/// The first one added is the launcher
+#[derive(Debug, Default)]
+pub struct SessionSet {
+    sessions: Vec<Session>,
+}
+
+impl SessionSet {
+    /// No sessions yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a session; returns false if it's already tracked
+    pub fn add(&mut self, id: String, label: String) -> bool {
+        self.insert(id, label, false)
+    }
+
+    /// Track a session another hook handed to us; like `add`, but the
+    /// default `EndWhen::Launcher` waits for it too
+    pub fn join(&mut self, id: String, label: String) -> bool {
+        self.insert(id, label, true)
+    }
+
+    fn insert(&mut self, id: String, label: String, joined: bool) -> bool {
+        if self.sessions.iter().any(|s| s.id == id) {
+            return false;
+        }
+        self.sessions.push(Session {
+            id,
+            label,
+            done: false,
+            joined,
+        });
+        true
+    }
+
+    /// Returns true if this is news, false for unknown or already-done sessions
+    pub fn mark_done(&mut self, id: &str) -> bool {
+        match self.sessions.iter_mut().find(|s| s.id == id) {
+            Some(session) if !session.done => {
+                session.done = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// True if `id` is the session whose hook launched the game
+    pub fn is_launcher(&self, id: &str) -> bool {
+        self.sessions.first().is_some_and(|s| s.id == id)
+    }
+
+    /// True once the sessions `when` cares about have all finished
+    pub fn finished(&self, when: EndWhen) -> bool {
+        match when {
+            EndWhen::Launcher => {
+                self.sessions.first().is_some_and(|s| s.done)
+                    && self.sessions.iter().filter(|s| s.joined).all(|s| s.done)
+            }
+            EndWhen::All => !self.sessions.is_empty() && self.sessions.iter().all(|s| s.done),
+        }
+    }
+
+    /// How many sessions are tracked
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// True before the first session is added
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Every session, launcher first
+    pub fn as_slice(&self) -> &[Session] {
+        &self.sessions
+    }
+}
+
+/// Session id for a transcript given without hook input
+/// Claude Code names transcripts `<session_id>.jsonl`
+pub fn session_id_for(transcript: &Path) -> String {
+    transcript
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| transcript.display().to_string())
+}
+
+/// Stats key for a transcript's project: the directory Claude Code keeps
+/// the project's transcripts in, e.g. `-home-me-api`
+pub fn project_dir(transcript: &Path) -> Option<String> {
+    transcript
+        .parent()
+        .and_then(Path::file_name)
+        .map(|dir| dir.to_string_lossy().into_owned())
+}
+
+/// HUD label: the project directory's name
+/// Prefers the hook's `cwd`; otherwise uses the transcript's directory
+/// as is. Claude Code names it after the project path with `/` turned
+/// into `-`, so there's no telling `my-project` from `my/project`.
+pub fn session_label(cwd: Option<&Path>, transcript: &Path) -> String {
+    if let Some(name) = cwd.and_then(Path::file_name) {
+        return name.to_string_lossy().into_owned();
+    }
+    project_dir(transcript).unwrap_or_else(|| session_id_for(transcript))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launcher_waits_for_joined_sessions() {
+        let mut sessions = SessionSet::new();
+        sessions.add("a".into(), "a".into());
+        sessions.add("extra".into(), "extra".into());
+        sessions.join("b".into(), "b".into());
+
+        sessions.mark_done("a");
+        assert!(!sessions.finished(EndWhen::Launcher));
+        sessions.mark_done("b");
+        assert!(sessions.finished(EndWhen::Launcher));
+        assert!(!sessions.finished(EndWhen::All));
+    }
+
+    #[test]
+    fn label_keeps_dashes_in_the_project_name() {
+        let transcript = Path::new("/home/me/.claude/projects/-home-me-my-project/abc.jsonl");
+        assert_eq!(session_label(None, transcript), "-home-me-my-project");
+        let cwd = Path::new("/home/me/my-project");
+        assert_eq!(session_label(Some(cwd), transcript), "my-project");
+    }
+}
@@ -1,34 +1,232 @@
-use std::fs;
-use std::path::PathBuf;
-use std::time::SystemTime;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs::{File, Metadata};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 
-/// Watches a transcript file for changes (indicating compaction is complete)
+/// The subset of a transcript record we care about.
+/// Everything else in the line is ignored.
+#[derive(Deserialize, Debug)]
+struct TranscriptRecord {
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    subtype: Option<String>,
+    #[serde(default, rename = "isCompactSummary")]
+    is_compact_summary: bool,
+    #[serde(default)]
+    uuid: Option<String>,
+}
+
+impl TranscriptRecord {
+    /// Claude Code writes a `compact_boundary` system record followed by a
+    /// user record flagged `isCompactSummary` once compaction is done
+    fn is_compaction_marker(&self) -> bool {
+        let boundary = self.kind.as_deref() == Some("system")
+            && self.subtype.as_deref() == Some("compact_boundary");
+        boundary || self.is_compact_summary
+    }
+}
+
+/// Tails a JSONL transcript and reports when a new compaction marker appears
 #[derive(Debug)]
 pub struct TranscriptWatcher {
     path: PathBuf,
-    initial_size: u64,
-    initial_mtime: SystemTime,
+    file_id: u64,                   // Inode of the file we've been reading
+    offset: u64,
+    partial: Vec<u8>,               // Trailing bytes of an unterminated line
+    known_markers: HashSet<String>, // Markers from earlier compactions
+    initial_len: u64,               // Size before compaction started
+    finished: bool,
 }
 
 impl TranscriptWatcher {
     /// Create a new watcher for the given path
-    /// Records initial size and mtime to detect changes
-    pub fn new(path: PathBuf) -> std::io::Result<Self> {
-        let metadata = fs::metadata(&path)?;
-        Ok(Self {
+    /// Reads the existing transcript once so markers left by earlier
+    /// compactions in the same session don't count as completion
+    pub fn new(path: PathBuf) -> io::Result<Self> {
+        let mut watcher = Self {
             path,
-            initial_size: metadata.len(),
-            initial_mtime: metadata.modified()?,
-        })
+            file_id: 0,
+            offset: 0,
+            partial: Vec::new(),
+            known_markers: HashSet::new(),
+            initial_len: 0,
+            finished: false,
+        };
+        let markers = watcher.read_new_markers()?;
+        watcher.known_markers.extend(markers);
+        watcher.initial_len = watcher.offset;
+        Ok(watcher)
     }
 
-    /// Check if the file has changed since we started watching
-    /// Detects any size change (compaction typically shrinks the file)
-    pub fn file_changed(&self) -> std::io::Result<bool> {
-        let metadata = fs::metadata(&self.path)?;
-        let current_size = metadata.len();
-        let current_mtime = metadata.modified()?;
+    /// The transcript being watched
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Transcript size in bytes when we started watching
+    pub fn initial_len(&self) -> u64 {
+        self.initial_len
+    }
+
+    /// Parse any records appended since the last call
+    /// Returns true once a compaction marker we haven't seen before shows up.
+    /// Lines that aren't valid records are skipped.
+    pub fn compaction_finished(&mut self) -> io::Result<bool> {
+        if self.finished {
+            return Ok(true);
+        }
+
+        for key in self.read_new_markers()? {
+            if !self.known_markers.contains(&key) {
+                self.finished = true;
+            }
+        }
+        Ok(self.finished)
+    }
+
+    /// Read from the last offset to EOF and return keys of any marker records
+    fn read_new_markers(&mut self) -> io::Result<Vec<String>> {
+        let mut file = File::open(&self.path)?;
+        let metadata = file.metadata()?;
+        let len = metadata.len();
+
+        // Replaced via rename, or shorter than where we left off: the file
+        // was rewritten, start over
+        let id = file_id(&metadata);
+        if id != self.file_id || len < self.offset {
+            self.file_id = id;
+            self.offset = 0;
+            self.partial.clear();
+        }
+        if len == self.offset {
+            return Ok(Vec::new());
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        self.offset += buf.len() as u64;
+        self.partial.extend_from_slice(&buf);
+
+        // Only complete lines are parsed; keep the remainder for next time
+        let Some(last_newline) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let complete: Vec<u8> = self.partial.drain(..=last_newline).collect();
+
+        let mut markers = Vec::new();
+        for line in complete.split(|&b| b == b'\n') {
+            let Ok(record) = serde_json::from_slice::<TranscriptRecord>(line) else {
+                continue;
+            };
+            if record.is_compaction_marker() {
+                // Records without a uuid are keyed by their raw contents
+                let key = record
+                    .uuid
+                    .unwrap_or_else(|| String::from_utf8_lossy(line).into_owned());
+                markers.push(key);
+            }
+        }
+        Ok(markers)
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.ino()
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &Metadata) -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+
+    const BOUNDARY: &str = r#"{"type":"system","subtype":"compact_boundary","uuid":"b1"}"#;
+    const SUMMARY: &str = r#"{"type":"user","isCompactSummary":true,"uuid":"s1"}"#;
+    const MESSAGE: &str = r#"{"type":"assistant","uuid":"m1"}"#;
+
+    /// A transcript in the temp dir, removed when dropped
+    struct Transcript(PathBuf);
+
+    impl Transcript {
+        fn new(name: &str, contents: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "clawd-runner-transcript-{}-{name}.jsonl",
+                std::process::id()
+            ));
+            fs::write(&path, contents).unwrap();
+            Self(path)
+        }
+
+        fn append(&self, text: &str) {
+            let mut file = OpenOptions::new().append(true).open(&self.0).unwrap();
+            file.write_all(text.as_bytes()).unwrap();
+        }
+
+        fn watch(&self) -> TranscriptWatcher {
+            TranscriptWatcher::new(self.0.clone()).unwrap()
+        }
+    }
+
+    impl Drop for Transcript {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn new_boundary_finishes() {
+        let transcript = Transcript::new("boundary", &format!("{MESSAGE}\n"));
+        let mut watcher = transcript.watch();
+        assert!(!watcher.compaction_finished().unwrap());
+
+        transcript.append(&format!("{MESSAGE}\n{BOUNDARY}\n"));
+        assert!(watcher.compaction_finished().unwrap());
+    }
+
+    #[test]
+    fn compact_summary_alone_finishes() {
+        let transcript = Transcript::new("summary", "");
+        let mut watcher = transcript.watch();
+        transcript.append(&format!("{SUMMARY}\n"));
+        assert!(watcher.compaction_finished().unwrap());
+    }
+
+    #[test]
+    fn markers_from_earlier_compactions_are_ignored() {
+        let transcript = Transcript::new("earlier", &format!("{BOUNDARY}\n{SUMMARY}\n"));
+        let mut watcher = transcript.watch();
+        assert_eq!(
+            watcher.initial_len(),
+            fs::metadata(&transcript.0).unwrap().len()
+        );
+        assert!(!watcher.compaction_finished().unwrap());
+
+        // The same records written again by a rewrite aren't new either
+        fs::write(&transcript.0, format!("{BOUNDARY}\n{SUMMARY}\n")).unwrap();
+        assert!(!watcher.compaction_finished().unwrap());
+    }
+
+    #[test]
+    fn partial_and_garbage_lines_are_skipped() {
+        let transcript = Transcript::new("partial", "");
+        let mut watcher = transcript.watch();
+
+        // Half a boundary record: nothing until the line is complete
+        let (head, tail) = BOUNDARY.split_at(20);
+        transcript.append(&format!("not json\n{{\"type\":\n{head}"));
+        assert!(!watcher.compaction_finished().unwrap());
 
-        Ok(current_size != self.initial_size || current_mtime > self.initial_mtime)
+        transcript.append(&format!("{tail}\n"));
+        assert!(watcher.compaction_finished().unwrap());
     }
 }
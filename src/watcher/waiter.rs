@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Instant;
+
+use super::{
+    Backend, CompactionHistory, CompactionProgress, EndWhen, ProgressStatus, Session, SessionSet,
+    TranscriptWatcher, WatchEvent,
+};
+use crate::control::{ControlSocket, SocketGuard};
+use crate::game::EndReason;
+
+/// Everything a run waits on: each session's transcript and control
+/// socket, the Claude Code process, and how far along the launching
+/// session's compaction is
+/// Add sessions, then call `poll` once a frame for a reason to end the run.
+#[derive(Debug)]
+pub struct Waiter {
+    started: Instant,
+    backend: Backend,
+    end_when: EndWhen,
+    sessions: SessionSet,
+    sockets: Vec<SocketGuard>, // Socket files go away with the waiter
+    progress: Option<CompactionProgress>,
+    tx: Sender<WatchEvent>,
+    events: Receiver<WatchEvent>,
+}
+
+impl Waiter {
+    /// Nothing to wait on yet; the run started at `started`
+    pub fn new(started: Instant, backend: Backend, end_when: EndWhen) -> Self {
+        let (tx, events) = mpsc::channel();
+        Self {
+            started,
+            backend,
+            end_when,
+            sessions: SessionSet::new(),
+            sockets: Vec::new(),
+            progress: None,
+            tx,
+            events,
+        }
+    }
+
+    /// Wait on a session given at startup
+    /// The first one is the launcher, and gets a time estimate from past
+    /// compactions of similar size.
+    pub fn add_session(&mut self, id: String, label: String, transcript: Option<PathBuf>) {
+        if !self.sessions.add(id.clone(), label) {
+            return;
+        }
+        if let Some(initial_len) = self.watch(id, transcript)
+            && self.sessions.len() == 1
+        {
+            let history = CompactionHistory::load();
+            self.progress = Some(CompactionProgress::new(self.started, initial_len, &history));
+        }
+    }
+
+    /// End the run if `pid` exits
+    pub fn watch_pid(&self, pid: u32) {
+        super::process::spawn(pid, self.tx.clone());
+    }
+
+    /// Handle the next thing a watcher reported, if any
+    /// Returns why the run should end, if it should.
+    pub fn poll(&mut self) -> Option<EndReason> {
+        match self.events.try_recv().ok()? {
+            WatchEvent::CompactionComplete(id) => {
+                if self.sessions.mark_done(&id)
+                    && self.sessions.is_launcher(&id)
+                    && let Some(ref p) = self.progress
+                {
+                    let elapsed = p.elapsed(Instant::now());
+                    let _ = CompactionHistory::path().and_then(|path| {
+                        CompactionHistory::record(&path, p.transcript_size(), elapsed)
+                    });
+                }
+                self.sessions
+                    .finished(self.end_when)
+                    .then_some(EndReason::Compaction)
+            }
+            WatchEvent::SessionJoined {
+                session_id,
+                label,
+                transcript,
+            } => {
+                if self.sessions.join(session_id.clone(), label) {
+                    self.watch(session_id, transcript);
+                }
+                None
+            }
+            WatchEvent::ProcessExited(_) => Some(EndReason::ClaudeExited),
+        }
+    }
+
+    /// Every session being waited on, launcher first
+    pub fn sessions(&self) -> &[Session] {
+        self.sessions.as_slice()
+    }
+
+    /// The launching session's progress, None if its transcript isn't watched
+    pub fn progress(&self, now: Instant) -> Option<ProgressStatus> {
+        self.progress.as_ref().map(|p| p.status(now))
+    }
+
+    /// Bind the session's control socket and watch its transcript
+    /// Returns the transcript's starting size if it's being watched
+    fn watch(&mut self, id: String, transcript: Option<PathBuf>) -> Option<u64> {
+        if let Ok(socket) = ControlSocket::bind(&id) {
+            self.sockets.push(socket.listen(self.tx.clone()));
+        }
+        let watcher = transcript.and_then(|p| TranscriptWatcher::new(p).ok())?;
+        let initial_len = watcher.initial_len();
+        super::spawn(watcher, id, self.backend, self.tx.clone()).ok()?;
+        Some(initial_len)
+    }
+}
@@ -0,0 +1,268 @@
+//! Golden snapshots of the rendered scene, characters and styles both.
+//! Run with UPDATE_SNAPSHOTS=1 to rewrite tests/snapshots/ after an
+//! intentional change, then review the diff.
+
+use clawd_runner::game::state::{Obstacle, PlayerState};
+use clawd_runner::render::sprites::ObstacleType;
+use clawd_runner::storage::SessionTotals;
+use clawd_runner::watcher::{ProgressStatus, Session};
+use clawd_runner::{EndReason, EndScreen, GameScene, GameState};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier};
+use ratatui::widgets::Widget;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const WIDTH: u16 = 60;
+const HEIGHT: u16 = 8;
+
+/// A quiet game at a fixed frame, so animations land the same every run
+fn game() -> GameState {
+    let mut game = GameState::new().with_terminal_width(WIDTH).with_seed(1);
+    game.frame_count = 40;
+    game.score = 42;
+    game
+}
+
+fn draw(area: Rect, widget: impl FnOnce(Rect, &mut Buffer)) -> Buffer {
+    let mut buf = Buffer::empty(area);
+    widget(area, &mut buf);
+    buf
+}
+
+fn scene(game: &GameState) -> Buffer {
+    draw(Rect::new(0, 0, WIDTH, HEIGHT), |area, buf| {
+        GameScene::new(game).render(area, buf)
+    })
+}
+
+/// Text grid, then the same grid with one letter per distinct style, then
+/// what each letter means
+fn snapshot_text(buf: &Buffer) -> String {
+    let area = buf.area;
+    let mut legend: BTreeMap<String, char> = BTreeMap::new();
+    legend.insert(describe_style(Color::Reset, Color::Reset, Modifier::empty()), '.');
+
+    let mut text = String::new();
+    let mut styles = String::new();
+    for y in area.top()..area.bottom() {
+        text.push('|');
+        styles.push('|');
+        for x in area.left()..area.right() {
+            let cell = &buf[(x, y)];
+            text.push_str(cell.symbol());
+            let style = describe_style(cell.fg, cell.bg, cell.modifier);
+            let next = (b'A' + legend.len() as u8 - 1) as char;
+            styles.push(*legend.entry(style).or_insert(next));
+        }
+        text.push_str("|\n");
+        styles.push_str("|\n");
+    }
+
+    let mut out = format!("{}x{}\n{text}\n{styles}\n", area.width, area.height);
+    let mut keys: Vec<_> = legend.iter().map(|(style, key)| (*key, style)).collect();
+    keys.sort();
+    for (key, style) in keys {
+        writeln!(out, "{key} = {style}").unwrap();
+    }
+    out
+}
+
+fn describe_style(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut parts = Vec::new();
+    if fg != Color::Reset {
+        parts.push(format!("fg={fg:?}"));
+    }
+    if bg != Color::Reset {
+        parts.push(format!("bg={bg:?}"));
+    }
+    if !modifier.is_empty() {
+        parts.push(format!("mod={modifier:?}"));
+    }
+    if parts.is_empty() {
+        "default".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Compare against tests/snapshots/<name>.snap, or rewrite it in update mode
+fn assert_snapshot(name: &str, buf: &Buffer) {
+    let actual = snapshot_text(buf);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.snap"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "no snapshot at {}; run with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "snapshot {name} changed (UPDATE_SNAPSHOTS=1 to accept)\n--- expected\n{expected}\n--- actual\n{actual}"
+    );
+}
+
+#[test]
+fn running() {
+    let mut game = game();
+    game.obstacles.push(Obstacle::new(40.0, ObstacleType::Small));
+    assert_snapshot("running", &scene(&game));
+}
+
+#[test]
+fn mid_jump() {
+    let mut game = game();
+    game.jump();
+    for _ in 0..4 {
+        game.tick();
+    }
+    game.obstacles.push(Obstacle::new(10.0, ObstacleType::Tall));
+    assert_snapshot("mid_jump", &scene(&game));
+}
+
+#[test]
+fn ducking_under_flying() {
+    let mut game = game();
+    game.toggle_duck();
+    assert_eq!(game.player.state, PlayerState::Ducking);
+    game.obstacles.push(Obstacle::new(9.0, ObstacleType::Flying));
+    assert_snapshot("ducking_under_flying", &scene(&game));
+}
+
+#[test]
+fn collision_flash() {
+    let mut game = game();
+    game.obstacles.push(Obstacle::new(10.0, ObstacleType::Double));
+    game.tick();
+    assert!(game.collision_flash > 0);
+    assert_snapshot("collision_flash", &scene(&game));
+}
+
+#[test]
+fn milestone_flash() {
+    let mut game = game();
+    game.score = 99;
+    game.tick();
+    assert!(game.milestone_flash > 0);
+    assert_snapshot("milestone_flash", &scene(&game));
+}
+
+#[test]
+fn compaction_progress() {
+    let game = game();
+    let status = ProgressStatus {
+        elapsed: Duration::from_secs(12),
+        remaining: Some(Duration::from_secs(40)),
+    };
+    let buf = draw(Rect::new(0, 0, WIDTH, HEIGHT), |area, buf| {
+        GameScene::new(&game)
+            .with_progress(Some(status))
+            .with_tag(Some("demo"))
+            .render(area, buf)
+    });
+    assert_snapshot("compaction_progress", &buf);
+}
+
+#[test]
+fn new_high_score() {
+    let mut game = game();
+    game.score = 120;
+    let buf = draw(Rect::new(0, 0, WIDTH, HEIGHT), |area, buf| {
+        GameScene::new(&game).with_best(Some(100)).render(area, buf)
+    });
+    assert_snapshot("new_high_score", &buf);
+}
+
+#[test]
+fn session_best() {
+    let game = game();
+    let session = SessionTotals {
+        runs: 2,
+        best: 340,
+        play_secs: 185,
+    };
+    let buf = draw(Rect::new(0, 0, WIDTH, HEIGHT), |area, buf| {
+        GameScene::new(&game)
+            .with_session(Some(session))
+            .with_tag(Some("demo"))
+            .render(area, buf)
+    });
+    assert_snapshot("session_best", &buf);
+}
+
+#[test]
+fn session_best_gives_way_to_sessions() {
+    let game = game();
+    let session = SessionTotals {
+        runs: 2,
+        best: 340,
+        play_secs: 185,
+    };
+    let sessions: Vec<Session> = [("api", true), ("web-frontend", false), ("billing", false)]
+        .into_iter()
+        .map(|(label, done)| Session {
+            id: label.to_string(),
+            label: label.to_string(),
+            done,
+            joined: false,
+        })
+        .collect();
+    let buf = draw(Rect::new(0, 0, WIDTH, HEIGHT), |area, buf| {
+        GameScene::new(&game)
+            .with_session(Some(session))
+            .with_sessions(&sessions)
+            .with_tag(Some("demo"))
+            .render(area, buf)
+    });
+    assert_snapshot("session_best_gives_way_to_sessions", &buf);
+}
+
+#[test]
+fn achievement_toast() {
+    let mut game = game();
+    game.show_toast("achievement unlocked: Limbo", 90);
+    assert_snapshot("achievement_toast", &scene(&game));
+}
+
+#[test]
+fn toast_under_mid_jump() {
+    let mut game = game();
+    game.jump();
+    for _ in 0..8 {
+        game.tick();
+    }
+    game.show_toast("achievement unlocked: Limbo · ducked under a flyer", 90);
+    assert_snapshot("toast_under_mid_jump", &scene(&game));
+}
+
+#[test]
+fn end_screen() {
+    let mut game = game();
+    game.collisions = 2;
+    game.obstacles_cleared = 5;
+    game.finish(EndReason::Compaction, 90, false);
+    let buf = draw(Rect::new(0, 0, WIDTH, HEIGHT), |area, buf| {
+        GameScene::new(&game).render(area, buf);
+        EndScreen::new(&game).render(area, buf);
+    });
+    assert_snapshot("end_screen", &buf);
+}
+
+#[test]
+fn tiny_terminal() {
+    let game = game();
+    let buf = draw(Rect::new(0, 0, 30, 4), |area, buf| {
+        GameScene::new(&game).render(area, buf)
+    });
+    assert_snapshot("tiny_terminal", &buf);
+}